@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub fn parse(stage: &str) -> Option<Self> {
+        match stage {
+            "parse" => Some(Self::Parse),
+            "part1" => Some(Self::Part1),
+            "part2" => Some(Self::Part2),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse => f.pad("parse"),
+            Self::Part1 => f.pad("part1"),
+            Self::Part2 => f.pad("part2"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// `samples` must not be empty.
+    pub fn new(samples: &mut [Duration]) -> Self {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+    pub bytes: usize,
+}
+
+impl Measurement {
+    /// Input bytes processed per second, in MB/s, at the median time.
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.stats.median.as_secs_f64();
+
+        if seconds == 0.0 {
+            return f64::INFINITY;
+        }
+
+        self.bytes as f64 / seconds / 1_000_000.0
+    }
+}
+
+/// Times each stage of `S` separately over `iterations` runs, parsing `data`
+/// afresh every time.
+pub fn bench<S: Solution>(data: &str, iterations: usize) -> Result<Vec<Measurement>, Error> {
+    let iterations = iterations.max(1);
+    let mut samples = [
+        Vec::with_capacity(iterations),
+        Vec::with_capacity(iterations),
+        Vec::with_capacity(iterations),
+    ];
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let input = S::parse(black_box(data))?;
+        samples[0].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(black_box(&input))?);
+        samples[1].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(black_box(&input))?);
+        samples[2].push(start.elapsed());
+    }
+
+    let measurements = [Stage::Parse, Stage::Part1, Stage::Part2]
+        .into_iter()
+        .zip(samples.iter_mut())
+        .map(|(stage, samples)| Measurement {
+            day: S::DAY,
+            stage,
+            stats: Stats::new(samples),
+            bytes: data.len(),
+        })
+        .collect();
+
+    Ok(measurements)
+}
+
+/// Median times from a previous run, keyed by day and stage.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: HashMap<(u8, Stage), Duration>,
+}
+
+impl Baseline {
+    const HEADER: &'static str = "day\tstage\tmedian_ns";
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let data = fs::read_to_string(path).map_err(|error| Error::io(path, error))?;
+
+        Self::parse(path, &data)
+    }
+
+    fn parse(path: &str, data: &str) -> Result<Self, Error> {
+        let mut baseline = Self::default();
+
+        for (index, line) in data.lines().enumerate() {
+            if line.is_empty() || line == Self::HEADER {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            let invalid = || Error::config(path, index + 1, "expected day, stage and median_ns");
+
+            if fields.len() != 3 {
+                return Err(invalid());
+            }
+
+            let day = fields[0].parse().map_err(|_| invalid())?;
+            let stage = Stage::parse(fields[1]).ok_or_else(invalid)?;
+            let median = fields[2].parse().map_err(|_| invalid())?;
+
+            baseline
+                .medians
+                .insert((day, stage), Duration::from_nanos(median));
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn save(path: &str, measurements: &[Measurement]) -> Result<(), Error> {
+        let mut data = String::from(Self::HEADER);
+
+        for measurement in measurements {
+            data.push_str(&format!(
+                "\n{}\t{}\t{}",
+                measurement.day,
+                measurement.stage,
+                measurement.stats.median.as_nanos()
+            ));
+        }
+        data.push('\n');
+
+        fs::write(path, data).map_err(|error| Error::io(path, error))
+    }
+
+    /// Relative change of the median against the baseline, e.g. `0.1` for
+    /// 10% slower. `None` when the baseline has no entry for it.
+    pub fn change(&self, measurement: &Measurement) -> Option<f64> {
+        let baseline = self.medians.get(&(measurement.day, measurement.stage))?;
+
+        if baseline.is_zero() {
+            return None;
+        }
+
+        Some(measurement.stats.median.as_secs_f64() / baseline.as_secs_f64() - 1.0)
+    }
+}
+
+/// Prints one row per measurement and returns how many of them are slower
+/// than the baseline by more than `threshold` (e.g. `0.1` for 10%).
+pub fn write_report(
+    out: &mut impl Write,
+    measurements: &[Measurement],
+    baseline: Option<&Baseline>,
+    threshold: f64,
+) -> io::Result<usize> {
+    let mut regressions = 0;
+
+    writeln!(
+        out,
+        "{:<4} {:<6} {:>12} {:>12} {:>12} {:>12}",
+        "day", "stage", "min", "median", "max", "MB/s"
+    )?;
+
+    for measurement in measurements {
+        write!(
+            out,
+            "{:<4} {:<6} {:>12} {:>12} {:>12} {:>12.2}",
+            measurement.day,
+            measurement.stage,
+            format!("{:.2?}", measurement.stats.min),
+            format!("{:.2?}", measurement.stats.median),
+            format!("{:.2?}", measurement.stats.max),
+            measurement.throughput()
+        )?;
+
+        match baseline.map(|baseline| baseline.change(measurement)) {
+            Some(Some(change)) => {
+                write!(out, " {:>+8.1}%", change * 100.0)?;
+
+                if change > threshold {
+                    regressions += 1;
+                    write!(out, " REGRESSION")?;
+                }
+            }
+            Some(None) => write!(out, " {:>9}", "new")?,
+            None => {}
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(day: u8, stage: Stage, median: u64) -> Measurement {
+        Measurement {
+            day,
+            stage,
+            stats: Stats {
+                min: Duration::from_nanos(median / 2),
+                median: Duration::from_nanos(median),
+                max: Duration::from_nanos(median * 2),
+            },
+            bytes: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut samples = [5, 1, 3].map(Duration::from_nanos);
+        let stats = Stats::new(&mut samples);
+
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.max, Duration::from_nanos(5));
+
+        let mut samples = [4, 1, 2, 8].map(Duration::from_nanos);
+        assert_eq!(Stats::new(&mut samples).median, Duration::from_nanos(3));
+    }
+
+    #[test]
+    fn test_throughput() {
+        let measurement = measurement(1, Stage::Parse, 1_000);
+        assert_eq!(measurement.throughput(), 1_000.0);
+    }
+
+    #[test]
+    fn test_baseline_change() {
+        let baseline = Baseline::parse(
+            "baseline.tsv",
+            "day\tstage\tmedian_ns\n1\tparse\t1000\n1\tpart1\t200\n",
+        )
+        .unwrap();
+
+        let change = baseline
+            .change(&measurement(1, Stage::Parse, 1_500))
+            .unwrap();
+        assert!((change - 0.5).abs() < 1e-9);
+
+        let change = baseline.change(&measurement(1, Stage::Part1, 100)).unwrap();
+        assert!((change + 0.5).abs() < 1e-9);
+
+        assert_eq!(baseline.change(&measurement(1, Stage::Part2, 100)), None);
+        assert_eq!(baseline.change(&measurement(2, Stage::Parse, 100)), None);
+    }
+
+    #[test]
+    fn test_invalid_baseline() {
+        match Baseline::parse("baseline.tsv", "1\tparse\t1000\n1\tpart3\t200\n") {
+            Err(Error::Config { line, .. }) => assert_eq!(line, 2),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_report_regressions() {
+        let baseline = Baseline::parse("baseline.tsv", "1\tparse\t1000\n1\tpart1\t1000\n").unwrap();
+        let measurements = vec![
+            measurement(1, Stage::Parse, 1_050),
+            measurement(1, Stage::Part1, 2_000),
+            measurement(1, Stage::Part2, 2_000),
+        ];
+
+        let mut out = Vec::new();
+        let regressions = write_report(&mut out, &measurements, Some(&baseline), 0.1).unwrap();
+        let report = String::from_utf8(out).unwrap();
+
+        assert_eq!(regressions, 1);
+        assert_eq!(report.lines().count(), 4);
+        assert!(report.lines().nth(2).unwrap().ends_with("REGRESSION"));
+        assert!(report.lines().nth(3).unwrap().ends_with("new"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("aoc_2022_baseline_{}.tsv", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        Baseline::save(path, &[measurement(3, Stage::Part2, 42)]).unwrap();
+        let baseline = Baseline::load(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            baseline.change(&measurement(3, Stage::Part2, 42)),
+            Some(0.0)
+        );
+    }
+}
@@ -1,80 +1,180 @@
-use std::fs;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
 
-struct ElfList {
+use crate::error::{Error, Line};
+use crate::input::Input;
+use crate::runner::Part;
+use crate::solution::{Answer, Solution};
+
+#[derive(Default)]
+pub struct ElfList {
     list: Vec<Elf>,
 }
 
 impl ElfList {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self { list: Vec::new() }
     }
 
-    fn add_elf(&mut self, elf: Elf) {
+    pub fn add_elf(&mut self, elf: Elf) {
         self.list.push(elf);
     }
 
-    fn get_top_elf_calories(&self) -> u32 {
-        self.list
-            .iter()
-            .map(|item| item.calories)
-            .max()
-            .unwrap()
+    pub fn elves(&self) -> &[Elf] {
+        &self.list
     }
 
-    fn get_top_3_calories(&self) -> u32 {
-        let mut calories_per_elf: Vec<u32> = self
-            .list
-            .iter()
-            .map(|item| item.calories)
-            .collect();
+    /// The `n` elves carrying the most calories, as `(elf index, calories)`
+    /// from most to least. On ties the elf that comes first wins.
+    pub fn top_n(&self, n: usize) -> Vec<(usize, u64)> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // Min-heap of the best `n` so far, so its root is the one to beat
+        let mut heap = BinaryHeap::with_capacity(n);
 
-        calories_per_elf.sort();
+        for (index, elf) in self.list.iter().enumerate() {
+            let entry = Reverse((elf.calories(), Reverse(index)));
 
-        calories_per_elf.iter().rev().take(3).sum()
+            if heap.len() < n {
+                heap.push(entry);
+            } else if let Some(mut weakest) = heap.peek_mut() {
+                if entry < *weakest {
+                    *weakest = entry;
+                }
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(index)))| (index, calories))
+            .collect()
     }
 }
-struct Elf {
-    calories: u32,
+
+#[derive(Default)]
+pub struct Elf {
+    items: Vec<u64>,
+    calories: u64,
 }
 
 impl Elf {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
+            items: Vec::new(),
             calories: 0,
         }
     }
 
-    fn add_calories(&mut self, calories: u32) {
-        self.calories += calories;
+    /// Returns the new total, or `None` when it would not fit in a `u64`,
+    /// in which case the elf is left unchanged.
+    pub fn add_calories(&mut self, calories: u64) -> Option<u64> {
+        self.calories = self.calories.checked_add(calories)?;
+        self.items.push(calories);
+
+        Some(self.calories)
+    }
+
+    /// Calories of each item, in the order they were listed.
+    pub fn items(&self) -> &[u64] {
+        &self.items
+    }
+
+    pub fn calories(&self) -> u64 {
+        self.calories
     }
 }
 
 pub struct Day1 {}
 
-impl Day1 {
-    pub fn run() {
-        let filename = "src/day1/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = ElfList;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const PART1_LABEL: &'static str = "Max calories carried by an elf";
+    const PART2_LABEL: &'static str = "Total calories from top 3";
+
+    fn parse(data: &str) -> Result<ElfList, Error> {
+        Self::parse_reader("<data>", data.as_bytes())
+    }
+
+    fn part1(elf_list: &ElfList) -> Result<u64, Error> {
+        elf_list
+            .top_n(1)
+            .first()
+            .map(|(_, calories)| *calories)
+            .ok_or_else(|| Error::solve(Self::DAY, "there are no elves"))
+    }
+
+    fn part2(elf_list: &ElfList) -> Result<u64, Error> {
+        elf_list
+            .top_n(3)
+            .iter()
+            .try_fold(0u64, |total, (index, calories)| {
+                total
+                    .checked_add(*calories)
+                    .ok_or_else(|| Error::overflow(Self::DAY, "elf", *index))
+            })
+    }
+
+    fn run(input: &Input, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+        let elf_list = Self::parse_reader(&input.to_string(), input.open()?)?;
 
+        Self::answers(&elf_list, parts)
+    }
+}
+
+impl Day1 {
+    /// Parses one line at a time, so the raw text is never held in memory.
+    /// Elves are separated by one or more blank lines, and the last one
+    /// needs no blank line after it. `input` names the reader in I/O errors.
+    pub fn parse_reader(input: &str, mut reader: impl BufRead) -> Result<ElfList, Error> {
         let mut elf_list = ElfList::new();
-        let mut elf = Elf::new();
+        let mut elf: Option<Elf> = None;
+        let mut buffer = String::new();
+        let mut number = 0;
 
-        for line in data.split('\n') {
-            if line.is_empty() {
-                elf_list.add_elf(elf);
-                elf = Elf::new();
-            } else {
-                elf.add_calories(line.parse::<u32>().unwrap());
+        loop {
+            buffer.clear();
+            number += 1;
+
+            let read = reader
+                .read_line(&mut buffer)
+                .map_err(|error| Error::io(input, error))?;
+
+            if read == 0 {
+                break;
             }
-        }
 
-        let max_calories: u32 = elf_list.get_top_elf_calories();
+            let text = buffer.strip_suffix('\n').unwrap_or(&buffer);
+            let text = text.strip_suffix('\r').unwrap_or(text);
+            let line = Line::new(Self::DAY, number, text);
+
+            if text.is_empty() {
+                if let Some(elf) = elf.take() {
+                    elf_list.add_elf(elf);
+                }
+            } else {
+                let calories = text
+                    .parse::<u64>()
+                    .map_err(|_| line.error(text, "invalid calories"))?;
 
-        println!("Part 1 - Max calories carried by an elf: {}", max_calories);
+                elf.get_or_insert_with(Elf::new)
+                    .add_calories(calories)
+                    .ok_or_else(|| Error::overflow(Self::DAY, "elf", elf_list.elves().len()))?;
+            }
+        }
 
-        let max_calories_top_3 = elf_list.get_top_3_calories();
+        if let Some(elf) = elf {
+            elf_list.add_elf(elf);
+        }
 
-        println!("Part 2 - Total calories from top 3: {}", max_calories_top_3);
+        Ok(elf_list)
     }
 }
 
@@ -122,26 +222,121 @@ mod tests {
     fn test_elf_calories() {
         let elf_list = generate_elf_list();
         
-        assert_eq!(elf_list.list[0].calories, 6000);
-        assert_eq!(elf_list.list[1].calories, 4000);
-        assert_eq!(elf_list.list[2].calories, 11000);
-        assert_eq!(elf_list.list[3].calories, 24000);
-        assert_eq!(elf_list.list[4].calories, 10000);
+        assert_eq!(elf_list.list[0].calories(), 6000);
+        assert_eq!(elf_list.list[1].calories(), 4000);
+        assert_eq!(elf_list.list[2].calories(), 11000);
+        assert_eq!(elf_list.list[3].calories(), 24000);
+        assert_eq!(elf_list.list[4].calories(), 10000);
 
     }
 
     #[test]
-    fn test_top_elf_calories() {
+    fn test_top_n() {
         let elf_list = generate_elf_list();
-        
-        assert_eq!(elf_list.get_top_elf_calories(), 24000);
+
+        assert_eq!(elf_list.top_n(1), [(3, 24000)]);
+        assert_eq!(elf_list.top_n(3), [(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(elf_list.top_n(10).len(), 5);
+        assert_eq!(elf_list.top_n(0), []);
+        assert_eq!(ElfList::new().top_n(3), []);
     }
 
     #[test]
-    fn test_top_3_elf_calories() {
-        let elf_list = generate_elf_list();
-        
-        assert_eq!(elf_list.get_top_3_calories(), 45000);
+    fn test_top_n_ties() {
+        let mut elf_list = ElfList::new();
+
+        for calories in [5, 7, 5, 7, 5] {
+            let mut elf = Elf::new();
+            elf.add_calories(calories);
+            elf_list.add_elf(elf);
+        }
+
+        assert_eq!(elf_list.top_n(3), [(1, 7), (3, 7), (0, 5)]);
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        let data = "1000\r\n2000\r\n\r\n\r\n4000\n\n5000";
+        let elf_list = Day1::parse_reader("<test>", data.as_bytes()).unwrap();
+
+        let calories: Vec<u64> = elf_list.list.iter().map(Elf::calories).collect();
+        assert_eq!(calories, [3000, 4000, 5000]);
+
+        let elf_list = Day1::parse_reader("<test>", "".as_bytes()).unwrap();
+        assert!(elf_list.list.is_empty());
+    }
+
+    #[test]
+    fn test_parse_reader_error() {
+        match Day1::parse_reader("<test>", &[b'1', b'\n', 0xff][..]) {
+            Err(Error::Io { input, .. }) => assert_eq!(input, "<test>"),
+            result => panic!("Unexpected result: {:?}", result.map(|_| ())),
+        }
+
+        match Day1::parse_reader("<test>", "1000\r\n\r\n3x00\r\n".as_bytes()) {
+            Err(Error::Parse { line, text, .. }) => {
+                assert_eq!(line, 3);
+                assert_eq!(text, "3x00");
+            }
+            result => panic!("Unexpected result: {:?}", result.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let input = Input::Inline(String::from("1000\n2000\n\n4000"));
+        let answers = Day1::run(&input, &[Part::One, Part::Two]).unwrap();
+
+        assert_eq!(answers[0].value, "4000");
+        assert_eq!(answers[1].value, "7000");
+    }
+
+    #[test]
+    fn test_wide_calories() {
+        let elf_list = Day1::parse("4294967295\n1\n\n4294967295\n\n1").unwrap();
+
+        assert_eq!(Day1::part1(&elf_list).unwrap(), 4_294_967_296);
+        assert_eq!(Day1::part2(&elf_list).unwrap(), 8_589_934_592);
     }
 
+    #[test]
+    fn test_calories_overflow() {
+        let mut elf = Elf::new();
+        assert_eq!(elf.add_calories(u64::MAX - 1), Some(u64::MAX - 1));
+        assert_eq!(elf.add_calories(2), None);
+        assert_eq!(elf.calories(), u64::MAX - 1);
+        assert_eq!(elf.items(), [u64::MAX - 1]);
+        assert_eq!(elf.add_calories(1), Some(u64::MAX));
+
+        match Day1::parse("1\n\n18446744073709551615\n1\n") {
+            Err(Error::Overflow { day, item, index }) => {
+                assert_eq!((day, item, index), (1, "elf", 1));
+            }
+            result => panic!("Unexpected result: {:?}", result.map(|_| ())),
+        }
+
+        let elf_list = Day1::parse("1\n\n18446744073709551615\n\n2").unwrap();
+        assert_eq!(Day1::part1(&elf_list).unwrap(), u64::MAX);
+
+        match Day1::part2(&elf_list) {
+            Err(Error::Overflow { index, .. }) => assert_eq!(index, 2),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        let error = Day1::parse("1000\n2000\n\n3x00\n").err().unwrap();
+
+        match error {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 4);
+                assert_eq!(column, 1);
+                assert_eq!(text, "3x00");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
 }
@@ -0,0 +1,244 @@
+use std::io::{self, Write};
+
+use super::lib::{Elf, ElfList};
+
+/// Percentiles included in every report, besides the median.
+const PERCENTILES: [usize; 5] = [10, 25, 75, 90, 99];
+
+/// Length of the longest histogram bar, in characters.
+const BAR_WIDTH: usize = 40;
+
+/// A range of calorie totals, both ends included, and how many elves carry
+/// a total in it.
+#[derive(Debug, PartialEq)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub elves: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+    pub elves: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// `(percent, calories)`, using the nearest-rank method.
+    pub percentiles: Vec<(usize, u64)>,
+    pub min_items: usize,
+    pub max_items: usize,
+    pub mean_items: f64,
+    pub histogram: Vec<Bucket>,
+}
+
+impl ElfList {
+    /// Summarises the calories carried by each elf, spreading the totals
+    /// over at most `buckets` equally wide histogram buckets. `None` when
+    /// there are no elves.
+    pub fn statistics(&self, buckets: usize) -> Option<Statistics> {
+        let elves = self.elves();
+
+        if elves.is_empty() {
+            return None;
+        }
+
+        let mut totals: Vec<u64> = elves.iter().map(Elf::calories).collect();
+        totals.sort_unstable();
+
+        let items: Vec<usize> = elves.iter().map(|elf| elf.items().len()).collect();
+        let count = totals.len();
+
+        let middle = count / 2;
+        let median = if count.is_multiple_of(2) {
+            (totals[middle - 1] as f64 + totals[middle] as f64) / 2.0
+        } else {
+            totals[middle] as f64
+        };
+
+        Some(Statistics {
+            elves: count,
+            min: totals[0],
+            max: totals[count - 1],
+            mean: totals.iter().map(|total| *total as f64).sum::<f64>() / count as f64,
+            median,
+            percentiles: PERCENTILES
+                .iter()
+                .map(|percent| (*percent, percentile(&totals, *percent)))
+                .collect(),
+            min_items: items.iter().copied().min().unwrap_or(0),
+            max_items: items.iter().copied().max().unwrap_or(0),
+            mean_items: items.iter().sum::<usize>() as f64 / count as f64,
+            histogram: histogram(&totals, buckets),
+        })
+    }
+}
+
+impl Statistics {
+    pub fn write_report(&self, out: &mut impl Write) -> io::Result<()> {
+        let percentiles: Vec<String> = self
+            .percentiles
+            .iter()
+            .map(|(percent, calories)| format!("p{} {}", percent, calories))
+            .collect();
+
+        writeln!(out, "{:<12} {}", "elves", self.elves)?;
+        writeln!(
+            out,
+            "{:<12} min {}, mean {:.1}, median {:.1}, max {}",
+            "calories", self.min, self.mean, self.median, self.max
+        )?;
+        writeln!(out, "{:<12} {}", "percentiles", percentiles.join(", "))?;
+        writeln!(
+            out,
+            "{:<12} min {}, mean {:.1}, max {}",
+            "items", self.min_items, self.mean_items, self.max_items
+        )?;
+
+        let most = self
+            .histogram
+            .iter()
+            .map(|bucket| bucket.elves)
+            .max()
+            .unwrap_or(0);
+
+        for bucket in &self.histogram {
+            let bar = (bucket.elves * BAR_WIDTH).checked_div(most).unwrap_or(0);
+
+            writeln!(
+                out,
+                "{:>10} - {:<10} {:>8} {}",
+                bucket.start,
+                bucket.end,
+                bucket.elves,
+                "#".repeat(bar)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `sorted` must not be empty.
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+
+    sorted[rank - 1]
+}
+
+/// `sorted` must not be empty.
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<Bucket> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+
+    // Wide enough for `buckets` to cover the range, without computing its
+    // length, which may not fit in a `u64`
+    let width = (max - min) / buckets.max(1) as u64 + 1;
+
+    let mut histogram: Vec<Bucket> = (min..=max)
+        .step_by(width as usize)
+        .map(|start| Bucket {
+            start,
+            end: start.saturating_add(width - 1).min(max),
+            elves: 0,
+        })
+        .collect();
+
+    for total in sorted {
+        histogram[((total - min) / width) as usize].elves += 1;
+    }
+
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::day1::Day1;
+    use crate::solution::Solution;
+
+    fn generate_statistics() -> Statistics {
+        let elf_list = Day1::parse(include_str!("example.txt")).unwrap();
+
+        elf_list.statistics(4).unwrap()
+    }
+
+    #[test]
+    fn test_statistics() {
+        let statistics = generate_statistics();
+
+        assert_eq!(statistics.elves, 5);
+        assert_eq!(statistics.min, 4000);
+        assert_eq!(statistics.max, 24000);
+        assert_eq!(statistics.mean, 11000.0);
+        assert_eq!(statistics.median, 10000.0);
+        assert_eq!(
+            statistics.percentiles,
+            [
+                (10, 4000),
+                (25, 6000),
+                (75, 11000),
+                (90, 24000),
+                (99, 24000)
+            ]
+        );
+        assert_eq!(statistics.min_items, 1);
+        assert_eq!(statistics.max_items, 3);
+        assert_eq!(statistics.mean_items, 2.0);
+
+        assert!(ElfList::new().statistics(4).is_none());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let statistics = generate_statistics();
+        let counts: Vec<(u64, u64, usize)> = statistics
+            .histogram
+            .iter()
+            .map(|bucket| (bucket.start, bucket.end, bucket.elves))
+            .collect();
+
+        assert_eq!(
+            counts,
+            [
+                (4000, 9000, 2),
+                (9001, 14001, 2),
+                (14002, 19002, 0),
+                (19003, 24000, 1)
+            ]
+        );
+
+        assert_eq!(
+            histogram(&[7, 7], 4),
+            [Bucket {
+                start: 7,
+                end: 7,
+                elves: 2
+            }]
+        );
+        assert_eq!(histogram(&[1, 2], 0).len(), 1);
+
+        let buckets = histogram(&[0, u64::MAX], 2);
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[1].end, u64::MAX);
+        assert_eq!(buckets[1].elves, 1);
+    }
+
+    #[test]
+    fn test_report() {
+        let mut out = Vec::new();
+        generate_statistics().write_report(&mut out).unwrap();
+        let report = String::from_utf8(out).unwrap();
+
+        assert_eq!(report.lines().count(), 8);
+        assert_eq!(
+            report.lines().nth(1).unwrap(),
+            "calories     min 4000, mean 11000.0, median 10000.0, max 24000"
+        );
+        assert_eq!(
+            report.lines().nth(7).unwrap(),
+            "     19003 - 24000             1 ####################"
+        );
+    }
+}
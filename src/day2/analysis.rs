@@ -0,0 +1,459 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use super::lib::{Day2, Game, MatchResult, Move, Round};
+use super::ruleset::Ruleset;
+use crate::error::Error;
+use crate::solution::Solution;
+
+/// How the second column of the strategy guide is read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reading {
+    /// The move to play, as in part 1.
+    Moves,
+    /// The result to aim for, as in part 2.
+    Results,
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Moves => f.pad("moves"),
+            Self::Results => f.pad("results"),
+        }
+    }
+}
+
+/// One way of reading the guide and the points it scores.
+#[derive(Debug, PartialEq)]
+pub struct Interpretation {
+    pub reading: Reading,
+    /// `(symbol, move or result name)` for each symbol of the second column.
+    pub mapping: Vec<(String, String)>,
+    pub points: u32,
+}
+
+/// Every interpretation of a strategy guide, best first.
+#[derive(Debug, Default, PartialEq)]
+pub struct Analysis {
+    pub interpretations: Vec<Interpretation>,
+}
+
+impl Analysis {
+    /// The most assignments of symbols a reading may have, as many as 8
+    /// symbols read as 8 moves. Each one is a line of the report.
+    pub const MAX_ASSIGNMENTS: usize = 40_320;
+
+    /// Prints one row per interpretation, flagging the best and worst ones.
+    pub fn write_report(&self, out: &mut impl Write) -> io::Result<()> {
+        let best = self
+            .interpretations
+            .iter()
+            .map(|interpretation| interpretation.points)
+            .max();
+        let worst = self
+            .interpretations
+            .iter()
+            .map(|interpretation| interpretation.points)
+            .min();
+
+        writeln!(out, "{:<8} {:<40} {:>8}", "reading", "mapping", "points")?;
+
+        for interpretation in &self.interpretations {
+            let mapping: Vec<String> = interpretation
+                .mapping
+                .iter()
+                .map(|(symbol, name)| format!("{}={}", symbol, name))
+                .collect();
+
+            write!(
+                out,
+                "{:<8} {:<40} {:>8}",
+                interpretation.reading,
+                mapping.join(" "),
+                interpretation.points
+            )?;
+
+            if Some(interpretation.points) == best {
+                write!(out, " best")?;
+            } else if Some(interpretation.points) == worst {
+                write!(out, " worst")?;
+            }
+
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Day2 {
+    /// Scores the guide under every assignment of the second column's symbols
+    /// to distinct moves and to distinct results. The symbols are the ones
+    /// `ruleset` already knows for each reading, and a reading with no
+    /// symbols is left out. Fails when a reading has more than
+    /// `Analysis::MAX_ASSIGNMENTS` assignments.
+    pub fn analyse(data: &str, ruleset: &Ruleset) -> Result<Analysis, Error> {
+        // The guide is only read once, each assignment re-maps its symbols
+        let rounds = Day2::parse_with(data, ruleset)?;
+        let cycle = ruleset.cycle();
+
+        let moves: Vec<Move> = cycle.moves().collect();
+        let results = [MatchResult::Loser, MatchResult::Draw, MatchResult::Winner];
+
+        let mut interpretations = interpret(
+            Reading::Moves,
+            &rounds,
+            ruleset,
+            &ruleset.player_symbols(),
+            &moves,
+            |movement| String::from(cycle.name(movement)),
+            |_, movement| movement,
+        )?;
+
+        interpretations.extend(interpret(
+            Reading::Results,
+            &rounds,
+            ruleset,
+            &ruleset.result_symbols(),
+            &results,
+            |match_result| String::from(match_result.name()),
+            |enemy_move, match_result| cycle.response(enemy_move, match_result),
+        )?);
+
+        interpretations.sort_by_key(|interpretation| std::cmp::Reverse(interpretation.points));
+
+        Ok(Analysis { interpretations })
+    }
+}
+
+/// Scores the rounds under every assignment of `symbols` to distinct
+/// `values`, where `respond` gives the player's move for the enemy's and a
+/// value.
+fn interpret<T: Copy>(
+    reading: Reading,
+    rounds: &[Round],
+    ruleset: &Ruleset,
+    symbols: &[String],
+    values: &[T],
+    name: impl Fn(T) -> String,
+    respond: impl Fn(Move, T) -> Move,
+) -> Result<Vec<Interpretation>, Error> {
+    if symbols.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if symbols.len() > values.len() {
+        return Err(Error::solve(
+            Day2::DAY,
+            format!(
+                "{} symbols cannot be read as {} distinct {}",
+                symbols.len(),
+                values.len(),
+                reading
+            ),
+        ));
+    }
+
+    let assignments = (values.len() - symbols.len() + 1..=values.len())
+        .try_fold(1_usize, |count, factor| count.checked_mul(factor))
+        .filter(|count| *count <= Analysis::MAX_ASSIGNMENTS);
+
+    if assignments.is_none() {
+        return Err(Error::solve(
+            Day2::DAY,
+            format!(
+                "{} symbols read as {} distinct {} have more than {} assignments",
+                symbols.len(),
+                values.len(),
+                reading,
+                Analysis::MAX_ASSIGNMENTS
+            ),
+        ));
+    }
+
+    // The position in `symbols` of each round's response
+    let positions = rounds
+        .iter()
+        .map(|round| {
+            symbols
+                .iter()
+                .position(|symbol| symbol == round.response())
+                .ok_or_else(|| {
+                    Error::solve(
+                        Day2::DAY,
+                        format!(
+                            "the response on line {} cannot be read as {}",
+                            round.line(),
+                            reading
+                        ),
+                    )
+                })
+        })
+        .collect::<Result<Vec<usize>, Error>>()?;
+
+    // How many rounds pair each enemy move with each symbol, so that an
+    // assignment is scored without going through the rounds again
+    let enemy_moves: Vec<Move> = ruleset.cycle().moves().collect();
+    let mut counts = vec![vec![0_u64; symbols.len()]; enemy_moves.len()];
+
+    for (round, position) in rounds.iter().zip(&positions) {
+        counts[round.enemy_move().index()][*position] += 1;
+    }
+
+    Permutations::new(values.len(), symbols.len())
+        .map(|assignment| {
+            let assignment: Vec<T> = assignment.into_iter().map(|index| values[index]).collect();
+
+            let mut total = Some(0_u64);
+
+            for (enemy_move, counts) in enemy_moves.iter().zip(&counts) {
+                for (value, count) in assignment.iter().zip(counts) {
+                    let player_move = respond(*enemy_move, *value);
+                    let match_result = ruleset.cycle().outcome(*enemy_move, player_move);
+                    let points = u64::from(ruleset.outcome_points(&match_result))
+                        + u64::from(ruleset.shape_points(&player_move));
+
+                    total = total.and_then(|total| points.checked_mul(*count)?.checked_add(total));
+                }
+            }
+
+            let points = match total.and_then(|total| u32::try_from(total).ok()) {
+                Some(points) => points,
+                // Replays the rounds to tell which one overflows
+                None => replay(rounds, &positions, &assignment, ruleset, &respond)?,
+            };
+
+            Ok(Interpretation {
+                reading,
+                mapping: symbols
+                    .iter()
+                    .zip(&assignment)
+                    .map(|(symbol, value)| (symbol.clone(), name(*value)))
+                    .collect(),
+                points,
+            })
+        })
+        .collect()
+}
+
+/// Plays the rounds under one assignment, for the points of a game.
+fn replay<T: Copy>(
+    rounds: &[Round],
+    positions: &[usize],
+    assignment: &[T],
+    ruleset: &Ruleset,
+    respond: impl Fn(Move, T) -> Move,
+) -> Result<u32, Error> {
+    let mut game = Game::with_ruleset(ruleset.clone());
+
+    for (round, position) in rounds.iter().zip(positions) {
+        let player_move = respond(round.enemy_move(), assignment[*position]);
+        game.play_round(&round.enemy_move(), &player_move)?;
+    }
+
+    Ok(game.points())
+}
+
+/// Every ordered selection of `k` distinct indices below `n`, in
+/// lexicographic order.
+struct Permutations {
+    /// The next selection, `None` once they are all taken.
+    indices: Option<Vec<usize>>,
+    used: Vec<bool>,
+}
+
+impl Permutations {
+    fn new(n: usize, k: usize) -> Self {
+        let mut used = vec![false; n];
+        let indices = (k <= n).then(|| (0..k).collect::<Vec<usize>>());
+
+        if let Some(indices) = &indices {
+            for index in indices {
+                used[*index] = true;
+            }
+        }
+
+        Self { indices, used }
+    }
+}
+
+impl Iterator for Permutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.indices.clone()?;
+        let indices = self.indices.as_mut().unwrap();
+        let k = indices.len();
+
+        // Bumps the rightmost position that can take a larger free index,
+        // then fills the positions after it with the smallest free ones
+        let mut position = k;
+
+        loop {
+            if position == 0 {
+                self.indices = None;
+                break;
+            }
+
+            position -= 1;
+            self.used[indices[position]] = false;
+
+            let larger = (indices[position] + 1..self.used.len()).find(|index| !self.used[*index]);
+
+            if let Some(larger) = larger {
+                indices[position] = larger;
+                self.used[larger] = true;
+
+                for next in &mut indices[position + 1..] {
+                    let smallest = self.used.iter().position(|used| !used).unwrap();
+                    *next = smallest;
+                    self.used[smallest] = true;
+                }
+
+                break;
+            }
+        }
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::day2::MoveCycle;
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(Permutations::new(3, 3).count(), 6);
+        assert_eq!(Permutations::new(3, 2).count(), 6);
+        assert_eq!(Permutations::new(8, 8).count(), Analysis::MAX_ASSIGNMENTS);
+        assert_eq!(
+            Permutations::new(3, 0).collect::<Vec<_>>(),
+            [Vec::<usize>::new()]
+        );
+        assert_eq!(Permutations::new(2, 3).count(), 0);
+        assert_eq!(
+            Permutations::new(3, 2).collect::<Vec<_>>(),
+            [[0, 1], [0, 2], [1, 0], [1, 2], [2, 0], [2, 1]]
+        );
+    }
+
+    #[test]
+    fn test_analyse() {
+        let interpretations = Day2::analyse(include_str!("example.txt"), &Ruleset::default())
+            .unwrap()
+            .interpretations;
+
+        assert_eq!(interpretations.len(), 12);
+
+        let find = |reading, mapping: &str| {
+            interpretations
+                .iter()
+                .find(|interpretation| {
+                    let names: Vec<&str> = interpretation
+                        .mapping
+                        .iter()
+                        .map(|(_, name)| name.as_str())
+                        .collect();
+
+                    interpretation.reading == reading && names.join(" ") == mapping
+                })
+                .unwrap()
+                .points
+        };
+
+        assert_eq!(find(Reading::Moves, "rock paper scissors"), 15);
+        assert_eq!(find(Reading::Results, "lose draw win"), 12);
+        assert!(interpretations
+            .windows(2)
+            .all(|pair| pair[0].points >= pair[1].points));
+    }
+
+    #[test]
+    fn test_single_reading() {
+        let mut ruleset = Ruleset::new(MoveCycle::rock_paper_scissors());
+        let cycle = ruleset.cycle().clone();
+        ruleset.set_enemy_move("A", cycle.find("rock").unwrap());
+
+        for (symbol, name) in [("X", "rock"), ("Y", "paper")] {
+            let movement = cycle.find(name).unwrap();
+            ruleset.set_player_move(symbol, movement);
+            ruleset.set_shape_points(movement, 1);
+        }
+
+        let interpretations = Day2::analyse("A X\nA Y", &ruleset).unwrap().interpretations;
+
+        assert_eq!(interpretations.len(), 6);
+        assert!(interpretations
+            .iter()
+            .all(|interpretation| interpretation.reading == Reading::Moves));
+        assert_eq!(interpretations[0].points, 6 + 3 + 2);
+
+        ruleset.set_match_result("Z", MatchResult::Winner);
+        assert!(Day2::analyse("A X\nA Y", &ruleset).is_err());
+    }
+
+    #[test]
+    fn test_too_many_symbols() {
+        let mut ruleset = Ruleset::default();
+        ruleset.set_player_move("W", ruleset.cycle().find("rock").unwrap());
+
+        match Day2::analyse(include_str!("example.txt"), &ruleset) {
+            Err(Error::Solve { reason, .. }) => {
+                assert_eq!(reason, "4 symbols cannot be read as 3 distinct moves")
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_too_many_assignments() {
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i"];
+        let mut ruleset = Ruleset::new(MoveCycle::new(&names).unwrap());
+        let cycle = ruleset.cycle().clone();
+        ruleset.set_enemy_move("A", cycle.find("a").unwrap());
+
+        for name in names {
+            ruleset.set_player_move(&name.to_uppercase(), cycle.find(name).unwrap());
+        }
+
+        match Day2::analyse("A A", &ruleset) {
+            Err(Error::Solve { reason, .. }) => assert_eq!(
+                reason,
+                "9 symbols read as 9 distinct moves have more than 40320 assignments"
+            ),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let mut ruleset = Ruleset::default();
+        ruleset.set_outcome_points(MatchResult::Winner, u32::MAX - 3);
+
+        // Only reading Y as paper and Z as scissors wins both rounds
+        match Day2::analyse("A Y\nB Z", &ruleset) {
+            Err(Error::Overflow { day, item, index }) => {
+                assert_eq!((day, item, index), (2, "round", 1))
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let analysis = Day2::analyse("A Y\nB X\nC Z", &Ruleset::default()).unwrap();
+
+        let mut out = Vec::new();
+        analysis.write_report(&mut out).unwrap();
+        let report = String::from_utf8(out).unwrap();
+
+        assert_eq!(report.lines().count(), 13);
+        assert!(report.lines().nth(1).unwrap().ends_with(" best"));
+        assert!(report.lines().last().unwrap().ends_with(" worst"));
+        assert!(report.contains("moves    X=rock Y=paper Z=scissors"));
+    }
+}
@@ -1,137 +1,429 @@
-use std::fs;
-
-struct Game {
+use super::ruleset::Ruleset;
+use super::transcript::{RoundRecord, Transcript};
+use crate::error::{Error, Line};
+use crate::input::Input;
+use crate::runner::Part;
+use crate::solution::{Answer, Configured, Solution};
+
+#[derive(Default)]
+pub struct Game {
     points: u32,
+    rounds: usize,
+    ruleset: Ruleset,
+    /// Only kept when the game was created with `with_transcript`.
+    records: Option<Vec<RoundRecord>>,
 }
 
 impl Game {
-    fn new() -> Self {
-        Self { points: 0 }
+    pub fn new() -> Self {
+        Self::with_ruleset(Ruleset::default())
     }
 
-    fn play_round(&mut self, enemy_move: &Moves, player_move: &Moves) {
-        let match_result = MatchResult::calculate(enemy_move, player_move);
-        self.points += match_result.points() + player_move.points();
+    pub fn with_ruleset(ruleset: Ruleset) -> Self {
+        Self {
+            points: 0,
+            rounds: 0,
+            ruleset,
+            records: None,
+        }
     }
-}
 
+    /// A game that records every round it plays.
+    pub fn with_transcript(ruleset: Ruleset) -> Self {
+        Self {
+            records: Some(Vec::new()),
+            ..Self::with_ruleset(ruleset)
+        }
+    }
+
+    /// Fails when the total no longer fits in a `u32`, which house rules
+    /// with large point tables can cause.
+    pub fn play_round(&mut self, enemy_move: &Move, player_move: &Move) -> Result<(), Error> {
+        let match_result = self.ruleset.cycle().outcome(*enemy_move, *player_move);
+        let shape_points = self.ruleset.shape_points(player_move);
+        let outcome_points = self.ruleset.outcome_points(&match_result);
+
+        self.points = self
+            .points
+            .checked_add(outcome_points)
+            .and_then(|points| points.checked_add(shape_points))
+            .ok_or_else(|| Error::overflow(Day2::DAY, "round", self.rounds))?;
+        self.rounds += 1;
+
+        if let Some(records) = &mut self.records {
+            records.push(RoundRecord {
+                round: self.rounds,
+                enemy_move: *enemy_move,
+                player_move: *player_move,
+                match_result,
+                shape_points,
+                outcome_points,
+                total: self.points,
+            });
+        }
+
+        Ok(())
+    }
 
-#[derive(Debug, PartialEq)]
-enum MatchResult {
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+
+    /// The rounds played so far, or `None` when the game is not recording.
+    pub fn transcript(&self) -> Option<Transcript<'_>> {
+        self.records
+            .as_deref()
+            .map(|records| Transcript::new(records, self.ruleset.cycle()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MatchResult {
     Winner,
     Loser,
     Draw,
 }
 
 impl MatchResult {
-    fn calculate(enemy_move: &Moves, player_move: &Moves) -> Self {
-        match (&enemy_move, &player_move) {
-            (Moves::Rock, Moves::Rock) => MatchResult::Draw,
-            (Moves::Rock, Moves::Paper) => MatchResult::Winner,
-            (Moves::Rock, Moves::Scissors) => MatchResult::Loser,
-            (Moves::Paper, Moves::Rock) => MatchResult::Loser,
-            (Moves::Paper, Moves::Paper) => MatchResult::Draw,
-            (Moves::Paper, Moves::Scissors) => MatchResult::Winner,
-            (Moves::Scissors, Moves::Rock) => MatchResult::Winner,
-            (Moves::Scissors, Moves::Paper) => MatchResult::Loser,
-            (Moves::Scissors, Moves::Scissors) => MatchResult::Draw,
-        }
+    /// The player's result in rock-paper-scissors.
+    pub fn calculate(enemy_move: &Move, player_move: &Move) -> Self {
+        MoveCycle::rock_paper_scissors().outcome(*enemy_move, *player_move)
     }
 
-    fn parse(result: &str) -> Self {
-        match result {
-            "X" => Self::Loser,
-            "Y" => Self::Draw,
-            "Z" => Self::Winner,
-            _ => panic!("Invalid match result"),
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "win" => Some(Self::Winner),
+            "lose" => Some(Self::Loser),
+            "draw" => Some(Self::Draw),
+            _ => None,
         }
     }
 
-    fn points(&self) -> u32 {
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::Winner => 6,
-            Self::Loser => 0,
-            Self::Draw => 3,
+            Self::Winner => "win",
+            Self::Loser => "lose",
+            Self::Draw => "draw",
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Moves {
-    Rock,
-    Paper,
-    Scissors,
+/// A move, as its position in the `MoveCycle` it comes from, which also
+/// names it. Moves are only obtained from a cycle, with `MoveCycle::find` or
+/// `MoveCycle::moves`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    index: usize,
+    /// Size of the cycle the move comes from, so that cycles can tell their
+    /// own moves from those of other games.
+    cycle_size: usize,
 }
 
+impl Move {
+    const fn new(index: usize, cycle_size: usize) -> Self {
+        Self { index, cycle_size }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// The moves of rock-paper-scissors, the default game, as they come from
+/// `MoveCycle::rock_paper_scissors`. They mean nothing in other cycles.
+pub struct Moves;
+
+#[allow(non_upper_case_globals)]
 impl Moves {
-    fn parse(movement: &str) -> Self {
-        match movement {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => panic!("Invalid movement"),
-        }
+    pub const Rock: Move = Move::new(0, 3);
+    pub const Scissors: Move = Move::new(1, 3);
+    pub const Paper: Move = Move::new(2, 3);
+
+    /// The move that gets `match_result` against the enemy's in
+    /// rock-paper-scissors.
+    pub fn generate(enemy_move: &Move, match_result: &MatchResult) -> Move {
+        MoveCycle::rock_paper_scissors().response(*enemy_move, *match_result)
     }
+}
 
-    fn points(&self) -> u32 {
-        match self {
-            Moves::Rock => 1,
-            Moves::Paper => 2,
-            Moves::Scissors => 3,
+/// The moves of an odd-sized cyclic dominance game, in order: each move beats
+/// the `(n - 1) / 2` moves after it, wrapping around, and loses to the rest.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoveCycle {
+    names: Vec<String>,
+}
+
+impl MoveCycle {
+    /// `None` unless there is an odd number of distinct names.
+    pub fn new(names: &[&str]) -> Option<Self> {
+        let distinct = names
+            .iter()
+            .enumerate()
+            .all(|(index, name)| !names[..index].contains(name));
+
+        if names.len().is_multiple_of(2) || !distinct {
+            return None;
         }
+
+        Some(Self {
+            names: names.iter().map(|name| String::from(*name)).collect(),
+        })
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(&["rock", "scissors", "paper"]).unwrap()
     }
 
-    fn generate(enemy_move: &Self, match_result: &MatchResult) -> Self {
-        match (enemy_move, match_result) {
-            (Moves::Rock, MatchResult::Winner) => Self::Paper,
-            (Moves::Rock, MatchResult::Loser) => Self::Scissors,
-            (Moves::Rock, MatchResult::Draw) => Self::Rock,
-            (Moves::Paper, MatchResult::Winner) => Self::Scissors,
-            (Moves::Paper, MatchResult::Loser) => Self::Rock,
-            (Moves::Paper, MatchResult::Draw) => Self::Paper,
-            (Moves::Scissors, MatchResult::Winner) => Self::Rock,
-            (Moves::Scissors, MatchResult::Loser) => Self::Paper,
-            (Moves::Scissors, MatchResult::Draw) => Self::Scissors,
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(&["rock", "scissors", "lizard", "paper", "spock"]).unwrap()
+    }
+
+    pub fn size(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        let size = self.size();
+
+        (0..size).map(move |index| Move::new(index, size))
+    }
+
+    /// Whether `movement` is one of this cycle's, rather than a move of
+    /// another game.
+    pub fn contains(&self, movement: Move) -> bool {
+        movement.cycle_size == self.size()
+    }
+
+    /// Panics when `movement` is not one of this cycle's, as do `outcome` and
+    /// `response`.
+    pub fn name(&self, movement: Move) -> &str {
+        &self.names[self.position(movement)]
+    }
+
+    pub fn find(&self, name: &str) -> Option<Move> {
+        self.names
+            .iter()
+            .position(|candidate| candidate == name)
+            .map(|index| Move::new(index, self.size()))
+    }
+
+    /// The player's result.
+    pub fn outcome(&self, enemy_move: Move, player_move: Move) -> MatchResult {
+        let size = self.size();
+        // How far the enemy's move comes after the player's in the cycle
+        let distance = (self.position(enemy_move) + size - self.position(player_move)) % size;
+
+        if distance == 0 {
+            MatchResult::Draw
+        } else if distance <= size / 2 {
+            MatchResult::Winner
+        } else {
+            MatchResult::Loser
         }
     }
+
+    /// The move that gets `match_result` against the enemy's. When several
+    /// do, the one closest to the enemy's move.
+    pub fn response(&self, enemy_move: Move, match_result: MatchResult) -> Move {
+        let size = self.size();
+        let enemy_move = self.position(enemy_move);
+
+        let index = match match_result {
+            MatchResult::Draw => enemy_move,
+            // Each move beats the ones right after it
+            MatchResult::Winner => (enemy_move + size - 1) % size,
+            MatchResult::Loser => (enemy_move + 1) % size,
+        };
+
+        Move::new(index, size)
+    }
+
+    fn position(&self, movement: Move) -> usize {
+        assert!(
+            self.contains(movement),
+            "{:?} is not a move of a {}-move cycle",
+            movement,
+            self.size()
+        );
+
+        movement.index
+    }
+}
+
+impl Default for MoveCycle {
+    fn default() -> Self {
+        Self::rock_paper_scissors()
+    }
+}
+
+/// A line of the strategy guide. The second column is read as a move in part 1
+/// and as the expected result in part 2, so both readings are kept. A ruleset
+/// may only define one of them, which is then only missed by the part using it.
+pub struct Round {
+    line: usize,
+    enemy_move: Move,
+    /// The symbol of the second column, as written.
+    response: String,
+    player_move: Option<Move>,
+    match_result: Option<MatchResult>,
+}
+
+impl Round {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn enemy_move(&self) -> Move {
+        self.enemy_move
+    }
+
+    pub fn response(&self) -> &str {
+        &self.response
+    }
+
+    fn player_move(&self) -> Result<Move, Error> {
+        self.player_move.ok_or_else(|| {
+            Error::solve(
+                Day2::DAY,
+                format!("the response on line {} is not a move", self.line),
+            )
+        })
+    }
+
+    fn match_result(&self) -> Result<MatchResult, Error> {
+        self.match_result.ok_or_else(|| {
+            Error::solve(
+                Day2::DAY,
+                format!("the response on line {} is not a result", self.line),
+            )
+        })
+    }
+}
+
+/// The rounds of a strategy guide, along with the rules they are scored by.
+pub struct StrategyGuide {
+    pub rounds: Vec<Round>,
+    pub ruleset: Ruleset,
 }
 
 pub struct Day2 {}
 
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = StrategyGuide;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const PART1_LABEL: &'static str = "Total points after game";
+    const PART2_LABEL: &'static str = "Total points after game";
+
+    fn parse(data: &str) -> Result<StrategyGuide, Error> {
+        Self::parse_guide(data, Ruleset::default())
+    }
+
+    fn part1(guide: &StrategyGuide) -> Result<u32, Error> {
+        Self::score_as_moves(&guide.rounds, &guide.ruleset)
+    }
+
+    fn part2(guide: &StrategyGuide) -> Result<u32, Error> {
+        Self::score_as_results(&guide.rounds, &guide.ruleset)
+    }
+
+    /// The settings are a `Ruleset` file.
+    fn configure(path: &str) -> Result<Configured, Error> {
+        let ruleset = Ruleset::load(path)?;
+
+        Ok(Box::new(move |input, parts| {
+            Self::run_with(input, ruleset.clone(), parts)
+        }))
+    }
+}
+
 impl Day2 {
-    pub fn run() {
-        let filename = "src/day2/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+    /// Like `run`, but reading and scoring the guide by house rules.
+    pub fn run_with(input: &Input, ruleset: Ruleset, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+        let guide = Self::parse_guide(&input.read()?, ruleset)?;
 
-        // Part 1
-        let mut game = Game::new();
+        Self::answers(&guide, parts)
+    }
 
-        for line in data.split('\n') {
-            let data: Vec<&str> = line.split(' ').take(2).collect();
+    pub fn parse_guide(data: &str, ruleset: Ruleset) -> Result<StrategyGuide, Error> {
+        Ok(StrategyGuide {
+            rounds: Self::parse_with(data, &ruleset)?,
+            ruleset,
+        })
+    }
 
-            let enemy_move = Moves::parse(data[0]);
-            let player_move = Moves::parse(data[1]);
+    /// Reads the strategy guide using the symbols of `ruleset`.
+    pub fn parse_with(data: &str, ruleset: &Ruleset) -> Result<Vec<Round>, Error> {
+        Line::enumerate(Self::DAY, data)
+            .map(|line| {
+                let mut data = line.text().split(' ');
+
+                let enemy_move = data.next().unwrap_or_default();
+                let enemy_move = ruleset
+                    .enemy_move(enemy_move)
+                    .ok_or_else(|| line.error(enemy_move, "invalid move"))?;
+
+                let response = data
+                    .next()
+                    .ok_or_else(|| line.missing("missing response"))?;
+                let player_move = ruleset.player_move(response);
+                let match_result = ruleset.match_result(response);
+
+                if player_move.is_none() && match_result.is_none() {
+                    return Err(line.error(response, "invalid response"));
+                }
+
+                Ok(Round {
+                    line: line.number(),
+                    enemy_move,
+                    response: String::from(response),
+                    player_move,
+                    match_result,
+                })
+            })
+            .collect()
+    }
 
-            game.play_round(&enemy_move, &player_move);
-        }
+    /// Total points when the second column is the move to play.
+    pub fn score_as_moves(rounds: &[Round], ruleset: &Ruleset) -> Result<u32, Error> {
+        Ok(Self::play_as_moves(rounds, Game::with_ruleset(ruleset.clone()))?.points)
+    }
 
-        println!("Part 1 - Total points after game: {}", game.points);
+    /// Total points when the second column is the result to aim for.
+    pub fn score_as_results(rounds: &[Round], ruleset: &Ruleset) -> Result<u32, Error> {
+        Ok(Self::play_as_results(rounds, Game::with_ruleset(ruleset.clone()))?.points)
+    }
 
-        // Part 2
-        let mut game = Game::new();
+    /// Plays every round on `game`, reading the second column as the move to
+    /// play. Pass a `Game::with_transcript` to see how the points add up.
+    pub fn play_as_moves(rounds: &[Round], mut game: Game) -> Result<Game, Error> {
+        for round in rounds {
+            game.play_round(&round.enemy_move, &round.player_move()?)?;
+        }
 
-        for line in data.split('\n') {
-            let data: Vec<&str> = line.split(' ').take(2).collect();
-            
-            let enemy_move = Moves::parse(data[0]);
-            let match_result = MatchResult::parse(data[1]);
+        Ok(game)
+    }
 
-            let player_move = Moves::generate(&enemy_move, &match_result);
+    /// Plays every round on `game`, reading the second column as the result
+    /// to aim for.
+    pub fn play_as_results(rounds: &[Round], mut game: Game) -> Result<Game, Error> {
+        for round in rounds {
+            let player_move = game
+                .ruleset
+                .cycle()
+                .response(round.enemy_move, round.match_result()?);
 
-            game.play_round(&enemy_move, &player_move);
+            game.play_round(&round.enemy_move, &player_move)?;
         }
 
-        println!("Part 2 - Total points after game: {}", game.points);
+        Ok(game)
     }
 }
 
@@ -141,26 +433,32 @@ mod tests {
 
     #[test]
     fn test_moves_parse() {
-        assert_eq!(Moves::Rock, Moves::parse("A"));
-        assert_eq!(Moves::Paper, Moves::parse("B"));
-        assert_eq!(Moves::Scissors, Moves::parse("C"));
-        assert_eq!(Moves::Rock, Moves::parse("X"));
-        assert_eq!(Moves::Paper, Moves::parse("Y"));
-        assert_eq!(Moves::Scissors, Moves::parse("Z"));
+        let ruleset = Ruleset::default();
+
+        assert_eq!(Some(Moves::Rock), ruleset.enemy_move("A"));
+        assert_eq!(Some(Moves::Paper), ruleset.enemy_move("B"));
+        assert_eq!(Some(Moves::Scissors), ruleset.enemy_move("C"));
+        assert_eq!(Some(Moves::Rock), ruleset.player_move("X"));
+        assert_eq!(Some(Moves::Paper), ruleset.player_move("Y"));
+        assert_eq!(Some(Moves::Scissors), ruleset.player_move("Z"));
     }
 
     #[test]
     fn test_moves_score() {
-        assert_eq!(Moves::Rock.points(), 1);
-        assert_eq!(Moves::Paper.points(), 2);
-        assert_eq!(Moves::Scissors.points(), 3);
+        let ruleset = Ruleset::default();
+
+        assert_eq!(ruleset.shape_points(&Moves::Rock), 1);
+        assert_eq!(ruleset.shape_points(&Moves::Paper), 2);
+        assert_eq!(ruleset.shape_points(&Moves::Scissors), 3);
     }
 
     #[test]
     fn test_match_result_score() {
-        assert_eq!(MatchResult::Draw.points(), 3);
-        assert_eq!(MatchResult::Winner.points(), 6);
-        assert_eq!(MatchResult::Loser.points(), 0);
+        let ruleset = Ruleset::default();
+
+        assert_eq!(ruleset.outcome_points(&MatchResult::Draw), 3);
+        assert_eq!(ruleset.outcome_points(&MatchResult::Winner), 6);
+        assert_eq!(ruleset.outcome_points(&MatchResult::Loser), 0);
     }
 
     #[test]
@@ -175,14 +473,152 @@ mod tests {
         assert_eq!(MatchResult::Draw, result);
     }
 
+    #[test]
+    fn test_move_cycle() {
+        assert_eq!(MoveCycle::new(&[]), None);
+        assert_eq!(MoveCycle::new(&["rock", "paper"]), None);
+        assert_eq!(MoveCycle::new(&["rock", "paper", "rock"]), None);
+
+        let cycle = MoveCycle::rock_paper_scissors_lizard_spock();
+        let [rock, scissors, lizard, paper, spock] =
+            ["rock", "scissors", "lizard", "paper", "spock"].map(|name| cycle.find(name).unwrap());
+
+        for (winner, loser) in [
+            (scissors, paper),
+            (paper, rock),
+            (rock, lizard),
+            (lizard, spock),
+            (spock, scissors),
+            (scissors, lizard),
+            (lizard, paper),
+            (paper, spock),
+            (spock, rock),
+            (rock, scissors),
+        ] {
+            assert_eq!(cycle.outcome(loser, winner), MatchResult::Winner);
+            assert_eq!(cycle.outcome(winner, loser), MatchResult::Loser);
+        }
+
+        assert_eq!(cycle.outcome(spock, spock), MatchResult::Draw);
+        assert_eq!(cycle.name(lizard), "lizard");
+    }
+
+    #[test]
+    fn test_move_cycle_response() {
+        for cycle in [
+            MoveCycle::rock_paper_scissors(),
+            MoveCycle::rock_paper_scissors_lizard_spock(),
+            MoveCycle::new(&["a", "b", "c", "d", "e", "f", "g"]).unwrap(),
+        ] {
+            for enemy_move in cycle.moves() {
+                for match_result in [MatchResult::Winner, MatchResult::Loser, MatchResult::Draw] {
+                    let player_move = cycle.response(enemy_move, match_result);
+                    assert_eq!(cycle.outcome(enemy_move, player_move), match_result);
+                }
+            }
+        }
+
+        assert_eq!(
+            Moves::generate(&Moves::Rock, &MatchResult::Winner),
+            Moves::Paper
+        );
+        assert_eq!(
+            Moves::generate(&Moves::Paper, &MatchResult::Loser),
+            Moves::Rock
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a move of a 5-move cycle")]
+    fn test_foreign_move() {
+        let cycle = MoveCycle::rock_paper_scissors_lizard_spock();
+
+        assert!(!cycle.contains(Moves::Paper));
+        cycle.outcome(cycle.find("spock").unwrap(), Moves::Paper);
+    }
+
     #[test]
     fn test_match_points() {
         let mut game = Game::new();
 
-        game.play_round(&Moves::Rock, &Moves::Paper);
-        game.play_round(&Moves::Paper, &Moves::Rock);
-        game.play_round(&Moves::Scissors, &Moves::Scissors);
+        game.play_round(&Moves::Rock, &Moves::Paper).unwrap();
+        game.play_round(&Moves::Paper, &Moves::Rock).unwrap();
+        game.play_round(&Moves::Scissors, &Moves::Scissors).unwrap();
 
         assert_eq!(game.points, 15);
+        assert!(game.transcript().is_none());
+    }
+
+    #[test]
+    fn test_house_rules() {
+        let mut ruleset = Ruleset::default();
+        ruleset.set_outcome_points(MatchResult::Draw, 6);
+        ruleset.set_enemy_move("R", Moves::Rock);
+        ruleset.set_player_move("P", Moves::Paper);
+        ruleset.set_match_result("P", MatchResult::Draw);
+
+        let rounds = Day2::parse_with("R P\nA X", &ruleset).unwrap();
+        assert_eq!(Day2::score_as_moves(&rounds, &ruleset).unwrap(), 8 + 7);
+        assert_eq!(Day2::score_as_results(&rounds, &ruleset).unwrap(), 7 + 3);
+
+        assert!(Day2::parse("R P").is_err());
+    }
+
+    #[test]
+    fn test_single_reading() {
+        let mut ruleset = Ruleset::new(MoveCycle::rock_paper_scissors());
+        ruleset.set_enemy_move("A", Moves::Rock);
+        ruleset.set_player_move("X", Moves::Paper);
+        ruleset.set_match_result("W", MatchResult::Winner);
+
+        let rounds = Day2::parse_with("A X\nA X", &ruleset).unwrap();
+        assert_eq!(Day2::score_as_moves(&rounds, &ruleset).unwrap(), 12);
+        assert!(Day2::score_as_results(&rounds, &ruleset).is_err());
+
+        let rounds = Day2::parse_with("A W", &ruleset).unwrap();
+        assert_eq!(Day2::score_as_results(&rounds, &ruleset).unwrap(), 6);
+        match Day2::score_as_moves(&rounds, &ruleset) {
+            Err(Error::Solve { reason, .. }) => assert!(reason.contains("line 1")),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        assert!(Day2::parse_with("A Y", &ruleset).is_err());
+    }
+
+    #[test]
+    fn test_invalid_round() {
+        assert_eq!(Ruleset::default().enemy_move("D"), None);
+        assert_eq!(Ruleset::default().match_result("A"), None);
+
+        match Day2::parse("A Y\nB X\nC A").err().unwrap() {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 3);
+                assert_eq!(column, 3);
+                assert_eq!(text, "A");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        match Day2::parse("A Y\nB").err().unwrap() {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 2);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_run_with() {
+        let mut ruleset = Ruleset::default();
+        ruleset.set_outcome_points(MatchResult::Draw, 10);
+
+        let input = Input::Inline(String::from(include_str!("example.txt")));
+        let answers = Day2::run_with(&input, ruleset, &[Part::One, Part::Two]).unwrap();
+
+        assert_eq!(answers[0].value, "22");
+        assert_eq!(answers[1].value, "19");
     }
-}
\ No newline at end of file
+}
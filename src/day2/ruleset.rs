@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fs;
+
+use super::lib::{MatchResult, Move, MoveCycle};
+use crate::error::{ConfigLine, Error};
+
+/// How a strategy guide is read and scored: which symbols stand for which
+/// enemy move, player move or expected result, and the points for each shape
+/// and outcome. The default is the puzzle's own rules.
+///
+/// Rules can be loaded from a file of `kind name: value` lines, where each
+/// line overrides the default:
+///
+/// ```text
+/// # Draws are worth as much as wins
+/// outcome draw: 6
+/// shape scissors: 5
+/// player W: rock
+/// ```
+///
+/// A leading `cycle: ...` rule switches to another game with the listed moves,
+/// starting from no symbols or shape points at all:
+///
+/// ```text
+/// cycle: rock, scissors, lizard, paper, spock
+/// enemy A: rock
+/// shape spock: 5
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ruleset {
+    cycle: MoveCycle,
+    enemy_moves: HashMap<String, Move>,
+    player_moves: HashMap<String, Move>,
+    match_results: HashMap<String, MatchResult>,
+    shape_points: HashMap<Move, u32>,
+    outcome_points: HashMap<MatchResult, u32>,
+}
+
+impl Ruleset {
+    /// Rules for `cycle` with no symbols or shape points yet, and the
+    /// puzzle's outcome points.
+    pub fn new(cycle: MoveCycle) -> Self {
+        let mut ruleset = Self {
+            cycle,
+            enemy_moves: HashMap::new(),
+            player_moves: HashMap::new(),
+            match_results: HashMap::new(),
+            shape_points: HashMap::new(),
+            outcome_points: HashMap::new(),
+        };
+
+        for (match_result, points) in [
+            (MatchResult::Loser, 0),
+            (MatchResult::Draw, 3),
+            (MatchResult::Winner, 6),
+        ] {
+            ruleset.set_outcome_points(match_result, points);
+        }
+
+        ruleset
+    }
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let data = fs::read_to_string(path).map_err(|error| Error::io(path, error))?;
+
+        Self::parse(path, &data)
+    }
+
+    fn parse(path: &str, data: &str) -> Result<Self, Error> {
+        let mut ruleset = Self::default();
+
+        for line in ConfigLine::enumerate(path, data) {
+            if let Some(names) = line.leading("cycle")? {
+                let names: Vec<&str> = names.split(',').map(str::trim).collect();
+                let cycle = MoveCycle::new(&names)
+                    .ok_or_else(|| line.error("expected an odd number of distinct moves"))?;
+
+                ruleset = Self::new(cycle);
+                continue;
+            }
+
+            let (kind, name, value) = line
+                .text()
+                .split_once(':')
+                .and_then(|(key, value)| {
+                    let (kind, name) = key.trim().split_once(' ')?;
+
+                    Some((kind, name.trim(), value.trim()))
+                })
+                .ok_or_else(|| line.error("expected 'kind name: value'"))?;
+
+            let movement = |name| {
+                ruleset
+                    .cycle
+                    .find(name)
+                    .ok_or_else(|| line.error("invalid move"))
+            };
+            let points = || value.parse().map_err(|_| line.error("invalid points"));
+
+            match kind {
+                "enemy" => ruleset.set_enemy_move(name, movement(value)?),
+                "player" => ruleset.set_player_move(name, movement(value)?),
+                "result" => ruleset.set_match_result(
+                    name,
+                    MatchResult::from_name(value).ok_or_else(|| line.error("invalid result"))?,
+                ),
+                "shape" => ruleset.set_shape_points(movement(name)?, points()?),
+                "outcome" => ruleset.set_outcome_points(
+                    MatchResult::from_name(name).ok_or_else(|| line.error("invalid result"))?,
+                    points()?,
+                ),
+                _ => return Err(line.error("expected enemy, player, result, shape or outcome")),
+            }
+        }
+
+        Ok(ruleset)
+    }
+
+    pub fn cycle(&self) -> &MoveCycle {
+        &self.cycle
+    }
+
+    /// Like the other setters taking a move, panics when `movement` is not a
+    /// move of the ruleset's cycle.
+    pub fn set_enemy_move(&mut self, symbol: &str, movement: Move) {
+        self.enemy_moves
+            .insert(String::from(symbol), self.own(movement));
+    }
+
+    pub fn set_player_move(&mut self, symbol: &str, movement: Move) {
+        self.player_moves
+            .insert(String::from(symbol), self.own(movement));
+    }
+
+    pub fn set_match_result(&mut self, symbol: &str, match_result: MatchResult) {
+        self.match_results
+            .insert(String::from(symbol), match_result);
+    }
+
+    pub fn set_shape_points(&mut self, movement: Move, points: u32) {
+        self.shape_points.insert(self.own(movement), points);
+    }
+
+    pub fn set_outcome_points(&mut self, match_result: MatchResult, points: u32) {
+        self.outcome_points.insert(match_result, points);
+    }
+
+    /// The symbols read as a player move, sorted.
+    pub fn player_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.player_moves.keys().cloned().collect();
+        symbols.sort();
+
+        symbols
+    }
+
+    /// The symbols read as a match result, sorted.
+    pub fn result_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.match_results.keys().cloned().collect();
+        symbols.sort();
+
+        symbols
+    }
+
+    pub fn enemy_move(&self, symbol: &str) -> Option<Move> {
+        self.enemy_moves.get(symbol).copied()
+    }
+
+    /// How the second column is read in part 1.
+    pub fn player_move(&self, symbol: &str) -> Option<Move> {
+        self.player_moves.get(symbol).copied()
+    }
+
+    /// How the second column is read in part 2.
+    pub fn match_result(&self, symbol: &str) -> Option<MatchResult> {
+        self.match_results.get(symbol).copied()
+    }
+
+    pub fn shape_points(&self, movement: &Move) -> u32 {
+        self.shape_points.get(movement).copied().unwrap_or(0)
+    }
+
+    pub fn outcome_points(&self, match_result: &MatchResult) -> u32 {
+        self.outcome_points.get(match_result).copied().unwrap_or(0)
+    }
+    fn own(&self, movement: Move) -> Move {
+        assert!(
+            self.cycle.contains(movement),
+            "{:?} is not a move of the ruleset's cycle",
+            movement
+        );
+
+        movement
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        let mut ruleset = Self::new(MoveCycle::rock_paper_scissors());
+
+        for (enemy, player, name, points) in [
+            ("A", "X", "rock", 1),
+            ("B", "Y", "paper", 2),
+            ("C", "Z", "scissors", 3),
+        ] {
+            let movement = ruleset.cycle.find(name).unwrap();
+            ruleset.set_enemy_move(enemy, movement);
+            ruleset.set_player_move(player, movement);
+            ruleset.set_shape_points(movement, points);
+        }
+
+        for (symbol, match_result) in [
+            ("X", MatchResult::Loser),
+            ("Y", MatchResult::Draw),
+            ("Z", MatchResult::Winner),
+        ] {
+            ruleset.set_match_result(symbol, match_result);
+        }
+
+        ruleset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::day2::{Day2, Moves};
+
+    #[test]
+    fn test_default() {
+        let ruleset = Ruleset::default();
+
+        assert_eq!(ruleset.enemy_move("B"), Some(Moves::Paper));
+        assert_eq!(ruleset.enemy_move("X"), None);
+        assert_eq!(ruleset.player_move("Z"), Some(Moves::Scissors));
+        assert_eq!(ruleset.match_result("Z"), Some(MatchResult::Winner));
+        assert_eq!(ruleset.shape_points(&Moves::Paper), 2);
+        assert_eq!(ruleset.outcome_points(&MatchResult::Draw), 3);
+    }
+
+    #[test]
+    fn test_parse() {
+        let data = "# House rules\n\noutcome draw: 6\nshape scissors: 5\nplayer W: rock\n";
+        let ruleset = Ruleset::parse("rules.txt", data).unwrap();
+
+        assert_eq!(ruleset.outcome_points(&MatchResult::Draw), 6);
+        assert_eq!(ruleset.outcome_points(&MatchResult::Winner), 6);
+        assert_eq!(ruleset.shape_points(&Moves::Scissors), 5);
+        assert_eq!(ruleset.player_move("W"), Some(Moves::Rock));
+        assert_eq!(ruleset.player_move("X"), Some(Moves::Rock));
+    }
+
+    #[test]
+    fn test_parse_cycle() {
+        let data = "cycle: rock, scissors, lizard, paper, spock\nenemy A: spock\nshape lizard: 4\n";
+        let ruleset = Ruleset::parse("rules.txt", data).unwrap();
+
+        let [lizard, paper] = ["lizard", "paper"].map(|name| ruleset.cycle().find(name).unwrap());
+
+        assert_eq!(ruleset.cycle().size(), 5);
+        assert_eq!(ruleset.enemy_move("A"), ruleset.cycle().find("spock"));
+        assert_eq!(ruleset.enemy_move("B"), None);
+        assert_eq!(ruleset.shape_points(&lizard), 4);
+        assert_eq!(ruleset.shape_points(&paper), 0);
+        assert_eq!(paper.index(), 3);
+        assert_eq!(ruleset.outcome_points(&MatchResult::Winner), 6);
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let data = "outcome win: 4294967293\n";
+        let ruleset = Ruleset::parse("rules.txt", data).unwrap();
+
+        // A win with paper is worth exactly `u32::MAX`
+        let rounds = Day2::parse_with("A Y", &ruleset).unwrap();
+        assert_eq!(Day2::score_as_moves(&rounds, &ruleset).unwrap(), u32::MAX);
+
+        let rounds = Day2::parse_with("A Y\nA X", &ruleset).unwrap();
+        match Day2::score_as_moves(&rounds, &ruleset) {
+            Err(Error::Overflow { day, item, index }) => {
+                assert_eq!((day, item, index), (2, "round", 1))
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let rounds = Day2::parse_with("A Z\nA Z", &ruleset).unwrap();
+        assert!(Day2::score_as_results(&rounds, &ruleset).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        for (data, line) in [
+            ("shape rock: 1\nshape spock: 2", 2),
+            ("outcome win 6", 1),
+            ("enemy A: rock\nresult X: tie", 2),
+            ("bonus rock: 1", 1),
+            ("shape rock: lots", 1),
+            ("cycle: rock, paper", 1),
+            ("outcome win: 7\ncycle: rock, paper, scissors", 2),
+        ] {
+            match Ruleset::parse("rules.txt", data) {
+                Err(Error::Config { line: actual, .. }) => assert_eq!(actual, line, "{}", data),
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+}
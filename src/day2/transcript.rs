@@ -0,0 +1,196 @@
+use std::io::{self, Write};
+
+use super::lib::{MatchResult, Move, MoveCycle};
+
+/// One round played by a recording `Game`, with the running total after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundRecord {
+    /// Starting at 1, like the lines of the strategy guide.
+    pub round: usize,
+    pub enemy_move: Move,
+    pub player_move: Move,
+    pub match_result: MatchResult,
+    pub shape_points: u32,
+    pub outcome_points: u32,
+    pub total: u32,
+}
+
+/// The rounds of a game, along with the cycle that names their moves.
+pub struct Transcript<'a> {
+    records: &'a [RoundRecord],
+    cycle: &'a MoveCycle,
+}
+
+impl<'a> Transcript<'a> {
+    pub fn new(records: &'a [RoundRecord], cycle: &'a MoveCycle) -> Self {
+        Self { records, cycle }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a RoundRecord> {
+        self.records.iter()
+    }
+
+    /// One aligned row per round, under a header.
+    pub fn write_table(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{:>6} {:<10} {:<10} {:<6} {:>6} {:>7} {:>8}",
+            "round", "enemy", "player", "result", "shape", "outcome", "total"
+        )?;
+
+        for record in self.iter() {
+            writeln!(
+                out,
+                "{:>6} {:<10} {:<10} {:<6} {:>6} {:>7} {:>8}",
+                record.round,
+                self.cycle.name(record.enemy_move),
+                self.cycle.name(record.player_move),
+                record.match_result.name(),
+                record.shape_points,
+                record.outcome_points,
+                record.total
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// The same columns as `write_table`, as comma-separated values.
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "round,enemy,player,result,shape,outcome,total")?;
+
+        for record in self.iter() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                record.round,
+                csv_field(self.cycle.name(record.enemy_move)),
+                csv_field(self.cycle.name(record.player_move)),
+                record.match_result.name(),
+                record.shape_points,
+                record.outcome_points,
+                record.total
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &Transcript<'a> {
+    type Item = &'a RoundRecord;
+    type IntoIter = std::slice::Iter<'a, RoundRecord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
+/// Quotes a field when it holds a comma, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::day2::{Day2, Game, Moves, Ruleset};
+    use crate::solution::Solution;
+
+    fn generate_game() -> Game {
+        let rounds = Day2::parse(include_str!("example.txt")).unwrap().rounds;
+
+        Day2::play_as_moves(&rounds, Game::with_transcript(Ruleset::default())).unwrap()
+    }
+
+    #[test]
+    fn test_transcript() {
+        let game = generate_game();
+        let transcript = game.transcript().unwrap();
+
+        let rounds: Vec<(usize, MatchResult, u32, u32, u32)> = transcript
+            .iter()
+            .map(|record| {
+                (
+                    record.round,
+                    record.match_result,
+                    record.shape_points,
+                    record.outcome_points,
+                    record.total,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            rounds,
+            [
+                (1, MatchResult::Winner, 2, 6, 8),
+                (2, MatchResult::Loser, 1, 0, 9),
+                (3, MatchResult::Draw, 3, 3, 15)
+            ]
+        );
+        assert_eq!(
+            (&transcript).into_iter().last().unwrap().total,
+            game.points()
+        );
+    }
+
+    #[test]
+    fn test_transcript_as_results() {
+        let rounds = Day2::parse(include_str!("example.txt")).unwrap().rounds;
+        let game =
+            Day2::play_as_results(&rounds, Game::with_transcript(Ruleset::default())).unwrap();
+
+        let player_moves: Vec<Move> = game
+            .transcript()
+            .unwrap()
+            .iter()
+            .map(|record| record.player_move)
+            .collect();
+
+        assert_eq!(player_moves, [Moves::Rock, Moves::Rock, Moves::Rock]);
+        assert_eq!(game.points(), 12);
+    }
+
+    #[test]
+    fn test_write_table() {
+        let mut out = Vec::new();
+        generate_game()
+            .transcript()
+            .unwrap()
+            .write_table(&mut out)
+            .unwrap();
+        let table = String::from_utf8(out).unwrap();
+
+        assert_eq!(table.lines().count(), 4);
+        assert_eq!(
+            table.lines().nth(1).unwrap(),
+            "     1 rock       paper      win         2       6        8"
+        );
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut out = Vec::new();
+        generate_game()
+            .transcript()
+            .unwrap()
+            .write_csv(&mut out)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "round,enemy,player,result,shape,outcome,total\n\
+             1,rock,paper,win,2,6,8\n\
+             2,paper,rock,lose,1,0,9\n\
+             3,scissors,scissors,draw,3,3,15\n"
+        );
+
+        assert_eq!(csv_field("say \"hi\", twice"), "\"say \"\"hi\"\", twice\"");
+    }
+}
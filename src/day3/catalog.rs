@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::error::{ConfigLine, Error};
+
+/// The item types rucksacks may hold and the priority of each. Every item
+/// type takes a slot, which is its bit in a `Compartment`, so a catalog holds
+/// at most `CAPACITY` of them.
+///
+/// The default is the puzzle's own: `a` to `z` have priorities 1 to 26 and
+/// `A` to `Z` 27 to 52. Catalogs can be loaded from a file of `item: priority`
+/// lines, each overriding the default:
+///
+/// ```text
+/// # Digits are worth the most
+/// 0: 100
+/// é: 5
+/// ```
+///
+/// To replace the default instead, a leading `sequence: ...` rule gives the
+/// listed items priorities 1, 2, 3... in order:
+///
+/// ```text
+/// sequence: 0123456789áéíóú
+/// ñ: 20
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemCatalog {
+    /// `(item, priority)` of each slot.
+    items: Vec<(char, u32)>,
+    /// The slot of each ASCII item, looked up directly as rucksacks are read.
+    ascii_slots: [Option<u8>; 128],
+    /// The slot of every other item.
+    slots: HashMap<char, usize>,
+}
+
+impl ItemCatalog {
+    pub const CAPACITY: usize = 128;
+
+    /// Items given priorities 1, 2, 3... in order. `None` when an item is
+    /// repeated or there are more than `CAPACITY`.
+    pub fn from_sequence(items: &str) -> Option<Self> {
+        let mut catalog = Self {
+            items: Vec::new(),
+            ascii_slots: [None; 128],
+            slots: HashMap::new(),
+        };
+
+        for (index, item) in items.chars().enumerate() {
+            if catalog.contains(item) || !catalog.insert(item, index as u32 + 1) {
+                return None;
+            }
+        }
+
+        Some(catalog)
+    }
+
+    /// The puzzle's catalog, shared so that it is only built once.
+    pub fn standard() -> &'static Self {
+        static STANDARD: OnceLock<ItemCatalog> = OnceLock::new();
+
+        STANDARD.get_or_init(Self::default)
+    }
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let data = fs::read_to_string(path).map_err(|error| Error::io(path, error))?;
+
+        Self::parse(path, &data)
+    }
+
+    fn parse(path: &str, data: &str) -> Result<Self, Error> {
+        let mut catalog = Self::default();
+
+        for line in ConfigLine::enumerate(path, data) {
+            if let Some(items) = line.leading("sequence")? {
+                catalog = Self::from_sequence(items).ok_or_else(|| {
+                    line.error(format!(
+                        "expected at most {} distinct items",
+                        Self::CAPACITY
+                    ))
+                })?;
+                continue;
+            }
+
+            let (item, priority) = line
+                .text()
+                .rsplit_once(':')
+                .ok_or_else(|| line.error("expected 'item: priority'"))?;
+
+            let mut chars = item.trim().chars();
+            let item = chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .ok_or_else(|| line.error("expected a single item"))?;
+            let priority = priority
+                .trim()
+                .parse()
+                .map_err(|_| line.error("invalid priority"))?;
+
+            if !catalog.insert(item, priority) {
+                return Err(line.error(format!(
+                    "the catalog is full, it holds at most {} items",
+                    Self::CAPACITY
+                )));
+            }
+        }
+
+        Ok(catalog)
+    }
+
+    /// Adds `item`, or changes its priority when it is already there.
+    /// Returns `false` when the catalog is full.
+    pub fn insert(&mut self, item: char, priority: u32) -> bool {
+        if let Some(slot) = self.slot(item) {
+            self.items[slot].1 = priority;
+        } else if self.items.len() < Self::CAPACITY {
+            match self.ascii_slots.get_mut(item as usize) {
+                Some(slot) => *slot = Some(self.items.len() as u8),
+                None => {
+                    self.slots.insert(item, self.items.len());
+                }
+            }
+
+            self.items.push((item, priority));
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// Number of item types.
+    pub fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        self.slot(item).is_some()
+    }
+
+    pub fn slot(&self, item: char) -> Option<usize> {
+        match self.ascii_slots.get(item as usize) {
+            Some(slot) => slot.map(usize::from),
+            None => self.slots.get(&item).copied(),
+        }
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.slot(item).map(|slot| self.items[slot].1)
+    }
+
+    /// The item type and priority in `slot`.
+    pub fn entry(&self, slot: usize) -> Option<(char, u32)> {
+        self.items.get(slot).copied()
+    }
+}
+
+impl Default for ItemCatalog {
+    fn default() -> Self {
+        Self::from_sequence("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard() {
+        let catalog = ItemCatalog::standard();
+
+        assert_eq!(catalog.size(), 52);
+        assert_eq!(catalog.priority('p'), Some(16));
+        assert_eq!(catalog.priority('L'), Some(38));
+        assert_eq!(catalog.priority('P'), Some(42));
+        assert_eq!(catalog.priority('v'), Some(22));
+        assert_eq!(catalog.priority('t'), Some(20));
+        assert_eq!(catalog.priority('s'), Some(19));
+        assert_eq!(catalog.priority('1'), None);
+        assert_eq!(catalog.entry(51), Some(('Z', 52)));
+        assert_eq!(catalog.entry(52), None);
+    }
+
+    #[test]
+    fn test_from_sequence() {
+        let catalog = ItemCatalog::from_sequence("0123456789éü").unwrap();
+
+        assert_eq!(catalog.priority('0'), Some(1));
+        assert_eq!(catalog.priority('ü'), Some(12));
+        assert_eq!(catalog.slot('é'), Some(10));
+        assert!(!catalog.contains('a'));
+
+        assert_eq!(ItemCatalog::from_sequence("abca"), None);
+
+        let items: String = (0..129)
+            .filter_map(|index| char::from_u32(0x100 + index))
+            .collect();
+        assert_eq!(ItemCatalog::from_sequence(&items), None);
+        assert!(ItemCatalog::from_sequence(&items[..items.len() - 2]).is_some());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut catalog = ItemCatalog::from_sequence("ab").unwrap();
+
+        assert!(catalog.insert('b', 10));
+        assert!(catalog.insert('ñ', 3));
+        assert_eq!(catalog.priority('b'), Some(10));
+        assert_eq!(catalog.slot('ñ'), Some(2));
+
+        // Letters, digits and the accented letters of Latin-1 all fit
+        let mut catalog = ItemCatalog::default();
+        let accented = ('À'..='ÿ').filter(|item| item.is_alphabetic());
+        for (index, item) in ('0'..='9').chain(accented).enumerate() {
+            assert!(catalog.insert(item, index as u32 + 53));
+        }
+        assert_eq!(catalog.size(), 124);
+
+        for item in 'Ā'..'Ą' {
+            assert!(catalog.insert(item, 1));
+        }
+        assert_eq!(catalog.size(), ItemCatalog::CAPACITY);
+        assert!(!catalog.insert('ą', 1));
+        assert!(catalog.insert('a', 1));
+    }
+
+    #[test]
+    fn test_parse() {
+        let data = "# Digits first\nsequence: 0123456789\né: 20\n::5\n";
+        let catalog = ItemCatalog::parse("items.txt", data).unwrap();
+
+        assert_eq!(catalog.priority('9'), Some(10));
+        assert_eq!(catalog.priority('é'), Some(20));
+        assert_eq!(catalog.priority(':'), Some(5));
+        assert_eq!(catalog.priority('a'), None);
+
+        let catalog = ItemCatalog::parse("items.txt", "a: 7\nÁ: 53").unwrap();
+        assert_eq!(catalog.priority('a'), Some(7));
+        assert_eq!(catalog.priority('Z'), Some(52));
+        assert_eq!(catalog.priority('Á'), Some(53));
+    }
+
+    #[test]
+    fn test_invalid() {
+        for (data, line) in [
+            ("a 7", 1),
+            ("ab: 7", 1),
+            (": 7", 1),
+            ("a: 7\nb: high", 2),
+            ("a: -1", 1),
+            ("sequence: aa", 1),
+            ("a: 7\nsequence: bc", 2),
+        ] {
+            match ItemCatalog::parse("items.txt", data) {
+                Err(Error::Config { line: actual, .. }) => assert_eq!(actual, line, "{}", data),
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+
+        let data: String = ('Ā'..'ō').map(|item| format!("{}: 1\n", item)).collect();
+        match ItemCatalog::parse("items.txt", &data) {
+            Err(Error::Config { line, reason, .. }) => {
+                assert_eq!(line, 77);
+                assert_eq!(reason, "the catalog is full, it holds at most 128 items");
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -1,94 +1,283 @@
-use std::collections::HashSet;
-use std::fs;
-
-struct Compartment {
-    items: HashSet<char>,
+use std::iter;
+use std::ops::{BitAnd, BitOr};
+
+use super::catalog::ItemCatalog;
+use crate::error::{Error, Line};
+use crate::input::Input;
+use crate::runner::Part;
+use crate::solution::{Answer, Configured, Solution};
+
+/// A set of item types, one bit per slot of an `ItemCatalog`. Items missing
+/// from the catalog are left out. The methods without a catalog use the
+/// puzzle's, where bit 0 is `a` and bit 51 is `Z`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Compartment {
+    items: u128,
 }
 
 impl Compartment {
-    fn new(items: &str) -> Self {
-        let mut data = HashSet::new();
+    pub fn new(items: &str) -> Self {
+        Self::with_catalog(items, ItemCatalog::standard())
+    }
+
+    pub fn with_catalog(items: &str, catalog: &ItemCatalog) -> Self {
+        let items = items
+            .chars()
+            .filter_map(|item| catalog.slot(item))
+            .fold(0, |items, slot| items | 1 << slot);
 
-        items.chars().for_each(|x| {
-            data.insert(x);
-        });
+        Self { items }
+    }
 
-        Self { items: data }
+    pub fn split(items: &str) -> [Self; 2] {
+        Self::split_with(items, ItemCatalog::standard())
     }
 
-    fn split(items: &str) -> [Self; 2] {
-        let half = items.len() / 2;
+    /// Splits the items in two halves, the middle one going to the second
+    /// when there is an odd number of them.
+    pub fn split_with(items: &str, catalog: &ItemCatalog) -> [Self; 2] {
+        let half = items
+            .char_indices()
+            .nth(items.chars().count() / 2)
+            .map_or(items.len(), |(index, _)| index);
 
-        let compartment_a = Compartment::new(&items[..half]);
-        let compartment_b = Compartment::new(&items[half..]);
+        let compartment_a = Compartment::with_catalog(&items[..half], catalog);
+        let compartment_b = Compartment::with_catalog(&items[half..], catalog);
 
         [compartment_a, compartment_b]
     }
 
-    fn get_common_items(&self, compartment: &Compartment) -> Self {
-        let mut common_items = HashSet::new();
+    pub fn get_common_items(&self, compartment: &Compartment) -> Self {
+        *self & *compartment
+    }
 
-        compartment.items.iter().for_each(|x| {
-            if self.items.contains(x) {
-                common_items.insert(*x);
-            }
-        });
+    pub fn union(&self, compartment: &Compartment) -> Self {
+        *self | *compartment
+    }
+
+    /// Number of distinct item types.
+    pub fn size(&self) -> u32 {
+        self.items.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items == 0
+    }
+
+    /// The item types, by slot.
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        self.items_with(ItemCatalog::standard())
+    }
+
+    pub fn items_with<'a>(&self, catalog: &'a ItemCatalog) -> impl Iterator<Item = char> + 'a {
+        self.entries(catalog).map(|(item, _)| item)
+    }
+
+    pub fn calculate_priority(&self) -> u32 {
+        self.calculate_priority_with(ItemCatalog::standard())
+    }
+
+    pub fn calculate_priority_with(&self, catalog: &ItemCatalog) -> u32 {
+        self.entries(catalog).map(|(_, priority)| priority).sum()
+    }
+
+    fn entries<'a>(&self, catalog: &'a ItemCatalog) -> impl Iterator<Item = (char, u32)> + 'a {
+        let mut items = self.items;
+
+        // Takes the lowest remaining bit each time
+        iter::from_fn(move || {
+            let slot = (items != 0).then(|| items.trailing_zeros() as usize)?;
+            items &= items - 1;
 
+            Some(slot)
+        })
+        .filter_map(|slot| catalog.entry(slot))
+    }
+}
+
+impl BitAnd for Compartment {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
         Self {
-            items: common_items,
+            items: self.items & other.items,
         }
     }
+}
 
-    fn calculate_priority(&self) -> u32 {
-        self.items.iter().map(Self::_get_item_priority).sum()
-    }
+impl BitOr for Compartment {
+    type Output = Self;
 
-    fn _get_item_priority(item: &char) -> u32 {
-        match item {
-            'a'..='z' => *item as u32 - 96, // a is 97 in ASCII -> 1,
-            'A'..='Z' => *item as u32 - 38, // A is 65 in ASCII -> 27,
-            _ => panic!("Invalid item"),
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            items: self.items | other.items,
         }
     }
 }
+
+/// The rucksacks of a list, as their two compartments, along with the
+/// catalog of their items.
+pub struct Rucksacks {
+    pub rucksacks: Vec<[Compartment; 2]>,
+    pub catalog: ItemCatalog,
+}
+
 pub struct Day3 {}
 
-impl Day3 {
-    pub fn run() {
-        let filename = "src/day3/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl Solution for Day3 {
+    const DAY: u8 = 3;
 
-        // Part 1
-        let mut total_priority = 0;
+    type Input = Rucksacks;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const PART1_LABEL: &'static str = "Total priority is";
+    const PART2_LABEL: &'static str = "Total groups priority is";
+
+    fn parse(data: &str) -> Result<Rucksacks, Error> {
+        Self::parse_rucksacks(data, ItemCatalog::standard().clone())
+    }
+
+    fn part1(list: &Rucksacks) -> Result<u32, Error> {
+        Ok(Self::total_priority(&list.rucksacks, &list.catalog))
+    }
+
+    fn part2(list: &Rucksacks) -> Result<u32, Error> {
+        let badges = Self::group_badges(&list.rucksacks, Self::GROUP_SIZE)?;
+
+        Ok(badges
+            .iter()
+            .map(|badges| badges.calculate_priority_with(&list.catalog))
+            .sum())
+    }
 
-        for line in data.split('\n') {
-            let [compartment_a, compartment_b] = Compartment::split(line);
+    /// The settings are an `ItemCatalog` file.
+    fn configure(path: &str) -> Result<Configured, Error> {
+        let catalog = ItemCatalog::load(path)?;
 
-            let common_items = compartment_a.get_common_items(&compartment_b);
-            
-            total_priority += common_items.calculate_priority();
+        Ok(Box::new(move |input, parts| {
+            Self::run_with(input, catalog.clone(), parts)
+        }))
+    }
+}
+
+impl Day3 {
+    /// Number of elves in each group of part 2.
+    pub const GROUP_SIZE: usize = 3;
+
+    /// Like `run`, but with the items and priorities of `catalog`.
+    pub fn run_with(
+        input: &Input,
+        catalog: ItemCatalog,
+        parts: &[Part],
+    ) -> Result<Vec<Answer>, Error> {
+        let list = Self::parse_rucksacks(&input.read()?, catalog)?;
+
+        Self::answers(&list, parts)
+    }
+
+    /// Reads the rucksacks like `parse_with`, then fails with the findings of
+    /// `validate` unless each rucksack and each group of `GROUP_SIZE` has
+    /// exactly one item to look for.
+    pub fn parse_rucksacks(data: &str, catalog: ItemCatalog) -> Result<Rucksacks, Error> {
+        let rucksacks = Self::parse_with(data, &catalog)?;
+
+        // Only looks for the findings when something is wrong
+        let valid = data
+            .lines()
+            .all(|text| text.chars().count().is_multiple_of(2))
+            && rucksacks.iter().all(|[compartment_a, compartment_b]| {
+                (*compartment_a & *compartment_b).size() == 1
+            })
+            && rucksacks.len().is_multiple_of(Self::GROUP_SIZE)
+            && Self::group_badges(&rucksacks, Self::GROUP_SIZE)?
+                .iter()
+                .all(|badges| badges.size() == 1);
+
+        if !valid {
+            let validation = Self::validate(data, Self::GROUP_SIZE, &catalog);
+            let findings: Vec<String> = validation
+                .findings
+                .iter()
+                .map(|finding| finding.to_string())
+                .collect();
+
+            return Err(Error::solve(
+                Self::DAY,
+                format!("invalid rucksacks: {}", findings.join("; ")),
+            ));
         }
 
-        println!("Part 1 - Total priority is: {}", total_priority);
+        Ok(Rucksacks { rucksacks, catalog })
+    }
 
-        // Part 2
+    /// Reads one rucksack per line, split like `Compartment::split_with`,
+    /// rejecting items missing from `catalog`.
+    pub fn parse_with(data: &str, catalog: &ItemCatalog) -> Result<Vec<[Compartment; 2]>, Error> {
+        Line::enumerate(Self::DAY, data)
+            .map(|line| {
+                let text = line.text();
+                let length = text.chars().count();
+                let mut items = text.char_indices();
+
+                // Reads the next `count` items into a compartment
+                let mut read = |count| {
+                    let mut compartment = Compartment::default();
+
+                    for (index, item) in items.by_ref().take(count) {
+                        let slot = catalog.slot(item).ok_or_else(|| {
+                            line.error(&text[index..index + item.len_utf8()], "invalid item")
+                        })?;
+
+                        compartment.items |= 1 << slot;
+                    }
+
+                    Ok(compartment)
+                };
+
+                Ok([read(length / 2)?, read(length - length / 2)?])
+            })
+            .collect()
+    }
+
+    /// Sum of the priorities of the items found in both compartments of each
+    /// rucksack.
+    pub fn total_priority(rucksacks: &[[Compartment; 2]], catalog: &ItemCatalog) -> u32 {
         let mut total_priority = 0;
-        let mut groups = Vec::with_capacity(3);
 
-        for line in data.split('\n') {
-            groups.push(Compartment::new(line));
+        for [compartment_a, compartment_b] in rucksacks {
+            let common_items = compartment_a.get_common_items(compartment_b);
 
-            if groups.len() == 3 {
-                let initial_common_items = groups[0].get_common_items(&groups[1]);
-                let common_items = initial_common_items.get_common_items(&groups[2]);
+            total_priority += common_items.calculate_priority_with(catalog);
+        }
 
-                total_priority += common_items.calculate_priority();
+        total_priority
+    }
 
-                groups.clear();
-            }
+    /// The items shared by every rucksack of each group of `size` consecutive
+    /// rucksacks. Rucksacks left over after the last full group are ignored.
+    pub fn group_badges(
+        rucksacks: &[[Compartment; 2]],
+        size: usize,
+    ) -> Result<Vec<Compartment>, Error> {
+        if size == 0 {
+            return Err(Error::solve(
+                Self::DAY,
+                "groups must have at least one rucksack",
+            ));
         }
 
-        println!("Part 2 - Total groups priority is: {}", total_priority);
+        Ok(rucksacks
+            .chunks_exact(size)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|[compartment_a, compartment_b]| compartment_a.union(compartment_b))
+                    .fold(Compartment { items: u128::MAX }, |badges, rucksack| {
+                        badges & rucksack
+                    })
+            })
+            .collect())
     }
 }
 
@@ -125,12 +314,12 @@ mod tests {
 
     #[test]
     fn test_item_priority() {
-        assert_eq!(Compartment::_get_item_priority(&'p'), 16);
-        assert_eq!(Compartment::_get_item_priority(&'L'), 38);
-        assert_eq!(Compartment::_get_item_priority(&'P'), 42);
-        assert_eq!(Compartment::_get_item_priority(&'v'), 22);
-        assert_eq!(Compartment::_get_item_priority(&'t'), 20);
-        assert_eq!(Compartment::_get_item_priority(&'s'), 19);
+        assert_eq!(ItemCatalog::standard().priority('p'), Some(16));
+        assert_eq!(ItemCatalog::standard().priority('L'), Some(38));
+        assert_eq!(ItemCatalog::standard().priority('P'), Some(42));
+        assert_eq!(ItemCatalog::standard().priority('v'), Some(22));
+        assert_eq!(ItemCatalog::standard().priority('t'), Some(20));
+        assert_eq!(ItemCatalog::standard().priority('s'), Some(19));
     }
 
     #[test]
@@ -138,4 +327,119 @@ mod tests {
         let compartment = Compartment::new("pLPvts");
         assert_eq!(compartment.calculate_priority(), 157);
     }
+
+    #[test]
+    fn test_set_operations() {
+        let compartment_a = Compartment::new("abcZ");
+        let compartment_b = Compartment::new("cdZZ");
+
+        assert_eq!(
+            compartment_a
+                .get_common_items(&compartment_b)
+                .items()
+                .collect::<String>(),
+            "cZ"
+        );
+        assert_eq!(
+            compartment_a
+                .union(&compartment_b)
+                .items()
+                .collect::<String>(),
+            "abcdZ"
+        );
+        assert_eq!(compartment_b.size(), 3);
+        assert!(Compartment::new("ab")
+            .get_common_items(&Compartment::new("AB"))
+            .is_empty());
+        assert!(Compartment::new("").is_empty());
+    }
+
+    #[test]
+    fn test_group_badges() {
+        let rucksacks = Day3::parse(include_str!("example.txt")).unwrap().rucksacks;
+
+        let badges: Vec<String> = Day3::group_badges(&rucksacks, 3)
+            .unwrap()
+            .iter()
+            .map(|badges| badges.items().collect())
+            .collect();
+        assert_eq!(badges, ["r", "Z"]);
+
+        let badges = Day3::group_badges(&rucksacks, 2).unwrap();
+        assert_eq!(badges.len(), 3);
+        assert_eq!(badges[0], Compartment::new("rsFMf"));
+
+        assert_eq!(
+            Day3::group_badges(&rucksacks, 1).unwrap()[0],
+            Compartment::new("vJrwpWtwJgWrhcsFMMfFFhFp")
+        );
+        assert!(Day3::group_badges(&rucksacks, 7).unwrap().is_empty());
+        assert!(Day3::group_badges(&rucksacks, 0).is_err());
+    }
+
+    #[test]
+    fn test_catalog() {
+        let catalog = ItemCatalog::from_sequence("0123456789éè").unwrap();
+
+        let rucksacks = Day3::parse_with("01é9\n123é\n2é3è", &catalog).unwrap();
+        assert_eq!(Day3::total_priority(&rucksacks, &catalog), 0);
+
+        let rucksacks = Day3::parse_with("é0é9\n1è2è\n9é5é", &catalog).unwrap();
+        assert_eq!(Day3::total_priority(&rucksacks, &catalog), 11 + 12 + 11);
+
+        let badges = Day3::group_badges(&rucksacks, 3).unwrap();
+        assert_eq!(badges[0].items_with(&catalog).collect::<String>(), "");
+
+        let badges = Day3::group_badges(&rucksacks[..1], 1).unwrap();
+        assert_eq!(badges[0].items_with(&catalog).collect::<String>(), "09é");
+        assert_eq!(badges[0].calculate_priority_with(&catalog), 1 + 10 + 11);
+
+        assert!(Day3::parse_with("abab", &catalog).is_err());
+        assert_eq!(Compartment::new("é0"), Compartment::default());
+    }
+
+    #[test]
+    fn test_invalid_item() {
+        assert_eq!(ItemCatalog::standard().priority('1'), None);
+
+        match Day3::parse("vJrwpWtwJgWrhcsFMMfFFhFp\nPmmdz-qPrVvPwwTWBwg")
+            .err()
+            .unwrap()
+        {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 6);
+                assert_eq!(text, "-");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_invalid_rucksacks() {
+        match Day3::parse("abcab\nab\n\nxyz").err().unwrap() {
+            Error::Solve { reason, .. } => assert_eq!(
+                reason,
+                "invalid rucksacks: line 1: odd number of items (5); \
+                 line 2: no item in both compartments; line 3: empty rucksack; \
+                 lines 1-3: no badge; line 4: odd number of items (3); \
+                 line 4: incomplete group of 1 rucksack"
+            ),
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_run_with() {
+        let mut catalog = ItemCatalog::default();
+        catalog.insert('p', 100);
+
+        let input = Input::Inline(String::from(include_str!("example.txt")));
+        let answers = Day3::run_with(&input, catalog, &[Part::One, Part::Two]).unwrap();
+
+        assert_eq!(answers[0].value, "241");
+        assert_eq!(answers[1].value, "70");
+    }
 }
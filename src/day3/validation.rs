@@ -0,0 +1,260 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use super::catalog::ItemCatalog;
+use super::lib::{Compartment, Day3};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    Empty,
+    /// The number of items, which cannot be split in two halves.
+    OddLength(usize),
+    /// `column` is 1-based and counts characters.
+    InvalidItem {
+        column: usize,
+        item: char,
+    },
+    NoSharedItem,
+    SharedItems(String),
+    NoBadge,
+    Badges(String),
+    /// The number of rucksacks left after the last full group.
+    IncompleteGroup(usize),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty rucksack"),
+            Self::OddLength(length) => write!(f, "odd number of items ({})", length),
+            Self::InvalidItem { column, item } => {
+                write!(f, "invalid item {:?} at column {}", item, column)
+            }
+            Self::NoSharedItem => write!(f, "no item in both compartments"),
+            Self::SharedItems(items) => write!(f, "several items in both compartments: {}", items),
+            Self::NoBadge => write!(f, "no badge"),
+            Self::Badges(items) => write!(f, "several badges: {}", items),
+            Self::IncompleteGroup(1) => write!(f, "incomplete group of 1 rucksack"),
+            Self::IncompleteGroup(rucksacks) => {
+                write!(f, "incomplete group of {} rucksacks", rucksacks)
+            }
+        }
+    }
+}
+
+/// An issue and the lines it concerns: a single line for a rucksack, or the
+/// lines of a group.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.first_line == self.last_line {
+            write!(f, "line {}: {}", self.first_line, self.issue)
+        } else {
+            write!(
+                f,
+                "lines {}-{}: {}",
+                self.first_line, self.last_line, self.issue
+            )
+        }
+    }
+}
+
+/// Everything wrong with a list of rucksacks. A group's findings follow
+/// those of its last rucksack.
+#[derive(Debug, Default, PartialEq)]
+pub struct Validation {
+    pub findings: Vec<Finding>,
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn write_report(&self, out: &mut impl Write) -> io::Result<()> {
+        for finding in &self.findings {
+            writeln!(out, "{}", finding)?;
+        }
+
+        match self.findings.len() {
+            0 => writeln!(out, "no issues"),
+            1 => writeln!(out, "1 issue"),
+            count => writeln!(out, "{} issues", count),
+        }
+    }
+}
+
+impl Day3 {
+    /// Checks each rucksack, and each group of `group_size` consecutive
+    /// rucksacks, without stopping at the first issue. Unlike `parse_with`,
+    /// this accepts any text.
+    pub fn validate(data: &str, group_size: usize, catalog: &ItemCatalog) -> Validation {
+        let mut validation = Validation::default();
+        let mut group = Vec::with_capacity(group_size);
+        let mut line = 0;
+
+        for (index, text) in data.lines().enumerate() {
+            line = index + 1;
+
+            let mut add = |issue| {
+                validation.findings.push(Finding {
+                    first_line: line,
+                    last_line: line,
+                    issue,
+                })
+            };
+
+            let invalid: Vec<(usize, char)> = text
+                .chars()
+                .enumerate()
+                .filter(|(_, item)| !catalog.contains(*item))
+                .collect();
+            let length = text.chars().count();
+
+            for (column, item) in &invalid {
+                add(Issue::InvalidItem {
+                    column: column + 1,
+                    item: *item,
+                });
+            }
+
+            if length == 0 {
+                add(Issue::Empty);
+            } else if !length.is_multiple_of(2) {
+                add(Issue::OddLength(length));
+            } else if invalid.is_empty() {
+                let [compartment_a, compartment_b] = Compartment::split_with(text, catalog);
+                let shared = compartment_a.get_common_items(&compartment_b);
+
+                match shared.size() {
+                    0 => add(Issue::NoSharedItem),
+                    1 => {}
+                    _ => add(Issue::SharedItems(shared.items_with(catalog).collect())),
+                }
+            }
+
+            if group_size == 0 {
+                continue;
+            }
+
+            group.push(Compartment::with_catalog(text, catalog));
+
+            if group.len() == group_size {
+                let badges = group[1..]
+                    .iter()
+                    .fold(group[0], |badges, rucksack| badges & *rucksack);
+
+                let issue = match badges.size() {
+                    0 => Some(Issue::NoBadge),
+                    1 => None,
+                    _ => Some(Issue::Badges(badges.items_with(catalog).collect())),
+                };
+
+                if let Some(issue) = issue {
+                    validation.findings.push(Finding {
+                        first_line: line + 1 - group_size,
+                        last_line: line,
+                        issue,
+                    });
+                }
+
+                group.clear();
+            }
+        }
+
+        if !group.is_empty() {
+            validation.findings.push(Finding {
+                first_line: line + 1 - group.len(),
+                last_line: line,
+                issue: Issue::IncompleteGroup(group.len()),
+            });
+        }
+
+        validation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid() {
+        let validation = Day3::validate(include_str!("example.txt"), 3, ItemCatalog::standard());
+
+        assert!(validation.is_valid());
+
+        let mut out = Vec::new();
+        validation.write_report(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "no issues\n");
+    }
+
+    #[test]
+    fn test_validate() {
+        let data = "vJrwpWtwJgWrhcsFMMfFFhFp\nab\n\nabcab\nab-ab\nabab\nxy";
+        let findings = Day3::validate(data, 3, ItemCatalog::standard()).findings;
+
+        let issues: Vec<(usize, usize, Issue)> = findings
+            .into_iter()
+            .map(|finding| (finding.first_line, finding.last_line, finding.issue))
+            .collect();
+
+        assert_eq!(
+            issues,
+            [
+                (2, 2, Issue::NoSharedItem),
+                (3, 3, Issue::Empty),
+                (1, 3, Issue::NoBadge),
+                (4, 4, Issue::OddLength(5)),
+                (
+                    5,
+                    5,
+                    Issue::InvalidItem {
+                        column: 3,
+                        item: '-'
+                    }
+                ),
+                (5, 5, Issue::OddLength(5)),
+                (6, 6, Issue::SharedItems(String::from("ab"))),
+                (4, 6, Issue::Badges(String::from("ab"))),
+                (7, 7, Issue::NoSharedItem),
+                (7, 7, Issue::IncompleteGroup(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_issues() {
+        assert_eq!(
+            Issue::IncompleteGroup(1).to_string(),
+            "incomplete group of 1 rucksack"
+        );
+        assert_eq!(
+            Issue::IncompleteGroup(2).to_string(),
+            "incomplete group of 2 rucksacks"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let mut out = Vec::new();
+        Day3::validate("ab\nééxx", 2, ItemCatalog::standard())
+            .write_report(&mut out)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "line 1: no item in both compartments\n\
+             line 2: invalid item 'é' at column 1\n\
+             line 2: invalid item 'é' at column 2\n\
+             lines 1-2: no badge\n\
+             4 issues\n"
+        );
+    }
+}
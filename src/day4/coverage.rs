@@ -0,0 +1,200 @@
+use std::io::{self, Write};
+
+use super::lib::Assignment;
+
+/// How the sections of the whole camp are covered by a set of assignments,
+/// from the lowest assigned section to the highest.
+#[derive(Debug, PartialEq)]
+pub struct Coverage {
+    pub assignments: usize,
+    /// The sections covered by at least one assignment, as disjoint ranges.
+    pub merged: Vec<Assignment>,
+    /// The sections between the merged ranges.
+    pub uncovered: Vec<Assignment>,
+    pub threshold: usize,
+    /// The sections covered by more than `threshold` assignments.
+    pub overbooked: Vec<Assignment>,
+    pub max_overlap: usize,
+    /// The sections covered by `max_overlap` assignments.
+    pub max_overlap_at: Vec<Assignment>,
+}
+
+/// A run of sections, both ends included, covered by the same number of
+/// assignments.
+struct Segment {
+    start: u32,
+    end: u32,
+    count: usize,
+}
+
+impl Coverage {
+    /// Sweeps over every assignment at once. `None` when there are none.
+    pub fn analyse<'a>(
+        assignments: impl IntoIterator<Item = &'a Assignment>,
+        threshold: usize,
+    ) -> Option<Self> {
+        // Where the count of assignments changes. An assignment stops counting
+        // right after its last section, which may be past `u32::MAX`
+        let mut events: Vec<(u64, isize)> = Vec::new();
+
+        for assignment in assignments {
+            events.push((assignment.lower_section() as u64, 1));
+            events.push((assignment.upper_section() as u64 + 1, -1));
+        }
+
+        if events.is_empty() {
+            return None;
+        }
+
+        events.sort_unstable();
+
+        let mut segments = Vec::new();
+        let mut count: isize = 0;
+
+        for (index, (section, change)) in events.iter().enumerate() {
+            count += change;
+
+            match events.get(index + 1) {
+                // Only the last event can be past `u32::MAX`
+                Some((next, _)) if next > section => segments.push(Segment {
+                    start: *section as u32,
+                    end: (next - 1) as u32,
+                    count: count as usize,
+                }),
+                _ => {}
+            }
+        }
+
+        let max_overlap = segments
+            .iter()
+            .map(|segment| segment.count)
+            .max()
+            .unwrap_or(0);
+
+        Some(Self {
+            assignments: events.len() / 2,
+            merged: ranges(&segments, |count| count > 0),
+            uncovered: ranges(&segments, |count| count == 0),
+            threshold,
+            overbooked: ranges(&segments, |count| count > threshold),
+            max_overlap,
+            max_overlap_at: ranges(&segments, |count| count == max_overlap),
+        })
+    }
+
+    pub fn write_report(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{:<12} {}", "assignments", self.assignments)?;
+        writeln!(out, "{:<12} {}", "coverage", join(&self.merged))?;
+        writeln!(out, "{:<12} {}", "uncovered", join(&self.uncovered))?;
+        writeln!(
+            out,
+            "{:<12} {} at {}",
+            "max overlap",
+            self.max_overlap,
+            join(&self.max_overlap_at)
+        )?;
+        writeln!(
+            out,
+            "{:<12} {}",
+            format!("over {}", self.threshold),
+            join(&self.overbooked)
+        )
+    }
+}
+
+/// Merges the consecutive segments whose count matches into ranges.
+fn ranges(segments: &[Segment], matches: impl Fn(usize) -> bool) -> Vec<Assignment> {
+    let mut ranges: Vec<Assignment> = Vec::new();
+
+    for segment in segments.iter().filter(|segment| matches(segment.count)) {
+        let current = Assignment::from_sections(segment.start, segment.end).unwrap();
+
+        match ranges.last_mut() {
+            Some(last) if last.is_adjacent(&current) => {
+                *last = last.union(&current)[0];
+            }
+            _ => ranges.push(current),
+        }
+    }
+
+    ranges
+}
+
+fn join(ranges: &[Assignment]) -> String {
+    if ranges.is_empty() {
+        return String::from("none");
+    }
+
+    let ranges: Vec<String> = ranges.iter().map(Assignment::to_string).collect();
+
+    ranges.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::day4::{Day4, Group};
+    use crate::solution::Solution;
+
+    fn generate_coverage(data: &str, threshold: usize) -> Coverage {
+        let groups = Day4::parse(data).unwrap();
+
+        Coverage::analyse(groups.iter().flat_map(Group::assignments), threshold).unwrap()
+    }
+
+    fn assignments(ranges: &[&str]) -> Vec<Assignment> {
+        ranges
+            .iter()
+            .map(|sections| Assignment::new(sections).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_coverage() {
+        let coverage = generate_coverage(include_str!("example.txt"), 6);
+
+        assert_eq!(coverage.assignments, 12);
+        assert_eq!(coverage.merged, assignments(&["2-9"]));
+        assert!(coverage.uncovered.is_empty());
+        assert_eq!(coverage.overbooked, assignments(&["4-6"]));
+        assert_eq!(coverage.max_overlap, 8);
+        assert_eq!(coverage.max_overlap_at, assignments(&["6-6"]));
+
+        assert!(Coverage::analyse(&[], 1).is_none());
+    }
+
+    #[test]
+    fn test_gaps() {
+        let coverage = generate_coverage("1-2,5-6\n8-9,5-5\n3-3,9-9", 1);
+
+        assert_eq!(coverage.merged, assignments(&["1-3", "5-6", "8-9"]));
+        assert_eq!(coverage.uncovered, assignments(&["4-4", "7-7"]));
+        assert_eq!(coverage.overbooked, assignments(&["5-5", "9-9"]));
+        assert_eq!(coverage.max_overlap_at, assignments(&["5-5", "9-9"]));
+
+        let coverage = generate_coverage("0-4294967295,4294967295-4294967295", 1);
+        assert_eq!(coverage.merged, assignments(&["0-4294967295"]));
+        assert_eq!(
+            coverage.max_overlap_at,
+            assignments(&["4294967295-4294967295"])
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let mut out = Vec::new();
+        generate_coverage("1-2,5-6\n8-9,5-5", 1)
+            .write_report(&mut out)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "assignments  4\n\
+             coverage     1-2, 5-6, 8-9\n\
+             uncovered    3-4, 7-7\n\
+             max overlap  2 at 5-5\n\
+             over 1       5-5\n"
+        );
+    }
+}
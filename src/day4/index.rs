@@ -0,0 +1,202 @@
+use super::lib::Assignment;
+
+/// An interval tree over a fixed list of assignments, answering overlap
+/// queries in `O(min(n, (k + 1) log n))` for `k` matches.
+///
+/// The assignments are kept sorted, and the tree is implicit: the root of
+/// each run is its middle element, which also stores the highest section in
+/// the run so that runs ending before a query can be skipped.
+#[derive(Clone, Debug, Default)]
+pub struct AssignmentIndex {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    assignment: Assignment,
+    /// Position in the list the index was built from.
+    position: usize,
+    /// Highest upper section in the run this node is the root of.
+    max_upper_section: u32,
+}
+
+impl AssignmentIndex {
+    pub fn new(assignments: &[Assignment]) -> Self {
+        let mut nodes: Vec<Node> = assignments
+            .iter()
+            .enumerate()
+            .map(|(position, assignment)| Node {
+                assignment: *assignment,
+                position,
+                max_upper_section: assignment.upper_section(),
+            })
+            .collect();
+
+        nodes.sort_unstable_by_key(|node| (node.assignment, node.position));
+
+        let mut index = Self { nodes };
+        index.build(0, index.nodes.len());
+
+        index
+    }
+
+    /// Number of assignments.
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Positions, in the list the index was built from, of the assignments
+    /// sharing a section with `sections`, in ascending order.
+    pub fn overlapping(&self, sections: &Assignment) -> Vec<usize> {
+        let mut positions = Vec::new();
+
+        self.search(0, self.nodes.len(), sections, &mut positions);
+        positions.sort_unstable();
+
+        positions
+    }
+
+    /// Positions of the assignments including `section`, in ascending order.
+    pub fn containing(&self, section: u32) -> Vec<usize> {
+        self.overlapping(&Assignment::from_sections(section, section).unwrap())
+    }
+
+    /// Fills in the highest section of the run `start..end`, returning it.
+    fn build(&mut self, start: usize, end: usize) -> Option<u32> {
+        if start >= end {
+            return None;
+        }
+
+        let middle = start + (end - start) / 2;
+        let left = self.build(start, middle);
+        let right = self.build(middle + 1, end);
+
+        let node = &mut self.nodes[middle];
+        node.max_upper_section = node.max_upper_section.max(left.max(right).unwrap_or(0));
+
+        Some(node.max_upper_section)
+    }
+
+    fn search(&self, start: usize, end: usize, sections: &Assignment, positions: &mut Vec<usize>) {
+        if start >= end {
+            return;
+        }
+
+        let middle = start + (end - start) / 2;
+        let node = &self.nodes[middle];
+
+        // Nothing in this run reaches the query
+        if node.max_upper_section < sections.lower_section() {
+            return;
+        }
+
+        self.search(start, middle, sections, positions);
+
+        // The rest of the run starts after the query, as the middle does
+        if node.assignment.lower_section() > sections.upper_section() {
+            return;
+        }
+
+        if node.assignment.overlaps(sections) {
+            positions.push(node.position);
+        }
+
+        self.search(middle + 1, end, sections, positions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::day4::{Day4, Group};
+    use crate::solution::Solution;
+
+    /// A small linear congruential generator, so that tests are repeatable.
+    fn generate_sections(seed: &mut u64, limit: u32) -> u32 {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        ((*seed >> 33) % limit as u64) as u32
+    }
+
+    fn generate_assignment(seed: &mut u64, limit: u32) -> Assignment {
+        let lower = generate_sections(seed, limit);
+        let length = generate_sections(seed, limit / 10 + 1);
+
+        Assignment::from_sections(lower, lower + length).unwrap()
+    }
+
+    fn naive_overlapping(assignments: &[Assignment], sections: &Assignment) -> Vec<usize> {
+        (0..assignments.len())
+            .filter(|position| assignments[*position].overlaps(sections))
+            .collect()
+    }
+
+    #[test]
+    fn test_example() {
+        let groups = Day4::parse(include_str!("example.txt")).unwrap();
+        let assignments: Vec<Assignment> = groups
+            .iter()
+            .flat_map(Group::assignments)
+            .copied()
+            .collect();
+        let index = AssignmentIndex::new(&assignments);
+
+        assert_eq!(index.size(), 12);
+        assert_eq!(index.containing(9), [5]);
+        assert_eq!(index.containing(1), Vec::<usize>::new());
+        assert_eq!(index.containing(6), [1, 4, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(
+            index.overlapping(&Assignment::new("1-2").unwrap()),
+            [0, 2, 6, 10]
+        );
+
+        assert!(AssignmentIndex::default().containing(0).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut seed = 2022;
+
+        for (count, limit) in [(1, 10), (2, 10), (50, 20), (500, 1000), (2000, 100_000)] {
+            let assignments: Vec<Assignment> = (0..count)
+                .map(|_| generate_assignment(&mut seed, limit))
+                .collect();
+            let index = AssignmentIndex::new(&assignments);
+
+            for _ in 0..200 {
+                let sections = generate_assignment(&mut seed, limit);
+
+                assert_eq!(
+                    index.overlapping(&sections),
+                    naive_overlapping(&assignments, &sections),
+                    "{}",
+                    sections
+                );
+
+                let section = generate_sections(&mut seed, limit);
+                let point = Assignment::from_sections(section, section).unwrap();
+
+                assert_eq!(
+                    index.containing(section),
+                    naive_overlapping(&assignments, &point)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates_and_bounds() {
+        let assignments: Vec<Assignment> = ["3-5", "3-5", "0-4294967295", "4294967295-4294967295"]
+            .iter()
+            .map(|sections| Assignment::new(sections).unwrap())
+            .collect();
+        let index = AssignmentIndex::new(&assignments);
+
+        assert_eq!(index.containing(4), [0, 1, 2]);
+        assert_eq!(index.containing(u32::MAX), [2, 3]);
+        assert_eq!(index.containing(0), [2]);
+    }
+}
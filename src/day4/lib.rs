@@ -1,87 +1,274 @@
-use std::fs;
+use std::fmt;
+use std::io::{self, Write};
 
-struct Assignment {
+use crate::error::{Error, Invalid, Line};
+use crate::solution::Solution;
+
+/// A closed range of sections, both ends included. Assignments are ordered
+/// by their lower section, then by their upper one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Assignment {
     lower_section: u32,
     upper_section: u32,
 }
 
 impl Assignment {
-    fn new(sections: &str) -> Self {
-        let sections: Vec<&str> = sections.split('-').take(2).collect();
+    pub fn new(sections: &str) -> Result<Self, Invalid<'_>> {
+        let (lower, upper) = sections
+            .split_once('-')
+            .ok_or_else(|| Invalid::new(sections, "expected a section range"))?;
+
+        let lower_section = lower
+            .parse()
+            .map_err(|_| Invalid::new(lower, "invalid section"))?;
+        let upper_section = upper
+            .parse()
+            .map_err(|_| Invalid::new(upper, "invalid section"))?;
+
+        Self::from_sections(lower_section, upper_section)
+            .ok_or_else(|| Invalid::new(sections, "section range is reversed"))
+    }
 
-        Self {
-            lower_section: sections[0].parse().unwrap(),
-            upper_section: sections[1].parse().unwrap(),
+    /// `None` when `lower_section` is above `upper_section`.
+    pub fn from_sections(lower_section: u32, upper_section: u32) -> Option<Self> {
+        if lower_section > upper_section {
+            return None;
         }
+
+        Some(Self {
+            lower_section,
+            upper_section,
+        })
+    }
+
+    pub fn lower_section(&self) -> u32 {
+        self.lower_section
+    }
+
+    pub fn upper_section(&self) -> u32 {
+        self.upper_section
+    }
+
+    /// Number of sections, at least 1.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        (self.upper_section - self.lower_section) as u64 + 1
     }
 
-    fn len(&self) -> usize {
-        (self.lower_section..=self.upper_section).count()
+    pub fn contains(&self, section: u32) -> bool {
+        self.lower_section <= section && section <= self.upper_section
     }
 
-    fn fully_contains(&self, assignment: &Self) -> bool {
-        if self.len() < assignment.len() {
-            return false;
+    /// The sections in both assignments.
+    pub fn intersection(&self, assignment: &Self) -> Option<Self> {
+        Self::from_sections(
+            self.lower_section.max(assignment.lower_section),
+            self.upper_section.min(assignment.upper_section),
+        )
+    }
+
+    /// The sections in either assignment: a single assignment when they
+    /// overlap or are adjacent, otherwise both, in order.
+    pub fn union(&self, assignment: &Self) -> Vec<Self> {
+        if self.overlaps(assignment) || self.is_adjacent(assignment) {
+            return vec![Self {
+                lower_section: self.lower_section.min(assignment.lower_section),
+                upper_section: self.upper_section.max(assignment.upper_section),
+            }];
         }
 
-        self.lower_section <= assignment.lower_section
-            && self.upper_section >= assignment.upper_section
+        let mut union = vec![*self, *assignment];
+        union.sort();
+
+        union
     }
 
-    fn overlaps(&self, assignment: &Self) -> bool {
-        assignment.lower_section <= self.upper_section
-            && assignment.upper_section >= self.lower_section
+    /// The sections in this assignment but not in the other one: none, the
+    /// part below or above it, or both parts, in order.
+    pub fn difference(&self, assignment: &Self) -> Vec<Self> {
+        let Some(intersection) = self.intersection(assignment) else {
+            return vec![*self];
+        };
+
+        let below = intersection
+            .lower_section
+            .checked_sub(1)
+            .and_then(|upper| Self::from_sections(self.lower_section, upper));
+        let above = intersection
+            .upper_section
+            .checked_add(1)
+            .and_then(|lower| Self::from_sections(lower, self.upper_section));
+
+        below.into_iter().chain(above).collect()
+    }
+
+    /// Whether one assignment starts right after the other ends, without
+    /// sharing any section.
+    pub fn is_adjacent(&self, assignment: &Self) -> bool {
+        self.upper_section.checked_add(1) == Some(assignment.lower_section)
+            || assignment.upper_section.checked_add(1) == Some(self.lower_section)
+    }
+
+    pub fn fully_contains(&self, assignment: &Self) -> bool {
+        self.intersection(assignment) == Some(*assignment)
+    }
+
+    pub fn overlaps(&self, assignment: &Self) -> bool {
+        self.intersection(assignment).is_some()
     }
 }
-pub struct Day4 {}
 
-impl Day4 {
-    pub fn run() {
-        let filename = "src/day4/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("{}-{}", self.lower_section, self.upper_section))
+    }
+}
 
-        // Part 1
-        let mut fully_contains_counter = 0;
+/// The assignments of a cleanup crew, from one line of input, in order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    assignments: Vec<Assignment>,
+}
 
-        for line in data.split('\n') {
-            let assignments: Vec<&str> = line.split(',').take(2).collect();
+impl Group {
+    pub fn new(assignments: Vec<Assignment>) -> Self {
+        Self { assignments }
+    }
+
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// Whether some assignment fully contains all the others, i.e. spans
+    /// every assigned section.
+    pub fn has_container(&self) -> bool {
+        let lower_section = self.assignments.iter().map(Assignment::lower_section).min();
+        let upper_section = self.assignments.iter().map(Assignment::upper_section).max();
+
+        lower_section
+            .zip(upper_section)
+            .and_then(|(lower, upper)| Assignment::from_sections(lower, upper))
+            .is_some_and(|span| self.assignments.contains(&span))
+    }
 
-            let assignment_a = Assignment::new(assignments[0]);
-            let assignment_b = Assignment::new(assignments[1]);
+    /// Whether any two assignments overlap.
+    pub fn has_overlap(&self) -> bool {
+        let mut assignments = self.assignments.clone();
+        assignments.sort_unstable();
 
-            let fully_contains = if assignment_a.len() >= assignment_b.len() {
-                assignment_a.fully_contains(&assignment_b)
-            } else {
-                assignment_b.fully_contains(&assignment_a)
-            };
+        // Once sorted, an assignment overlapping any earlier one overlaps the
+        // one reaching the highest section so far
+        let mut highest: Option<u32> = None;
 
-            if fully_contains {
-                fully_contains_counter += 1;
+        for assignment in assignments {
+            if highest.is_some_and(|upper| assignment.lower_section() <= upper) {
+                return true;
             }
+
+            highest = highest.max(Some(assignment.upper_section()));
         }
 
-        println!(
-            "Part 1 - Total fully contains pairs: {}",
-            fully_contains_counter
-        );
+        false
+    }
+
+    /// Every pair of overlapping members, as 0-based indices with the first
+    /// one lower.
+    pub fn overlapping_members(&self) -> Vec<(usize, usize)> {
+        let mut members = Vec::new();
+
+        for (index_a, assignment_a) in self.assignments.iter().enumerate() {
+            for (index_b, assignment_b) in self.assignments.iter().enumerate().skip(index_a + 1) {
+                if assignment_a.overlaps(assignment_b) {
+                    members.push((index_a, index_b));
+                }
+            }
+        }
 
-        // Part 2
-        let mut overlaps_counter = 0;
+        members
+    }
+}
 
-        for line in data.split('\n') {
-            let assignments: Vec<&str> = line.split(',').take(2).collect();
+pub struct Day4 {}
 
-            let assignment_a = Assignment::new(assignments[0]);
-            let assignment_b = Assignment::new(assignments[1]);
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Group>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PART1_LABEL: &'static str = "Total fully contains groups";
+    const PART2_LABEL: &'static str = "Total overlap groups";
+
+    fn parse(data: &str) -> Result<Vec<Group>, Error> {
+        Line::enumerate(Self::DAY, data)
+            .map(|line| {
+                let assignments = line
+                    .text()
+                    .split(',')
+                    .map(|assignment| {
+                        Assignment::new(assignment).map_err(|invalid| line.invalid(invalid))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if assignments.len() < 2 {
+                    return Err(line.missing("missing second assignment"));
+                }
+
+                Ok(Group::new(assignments))
+            })
+            .collect()
+    }
 
-            let overlaps = assignment_a.overlaps(&assignment_b);
+    fn part1(groups: &Vec<Group>) -> Result<usize, Error> {
+        Ok(groups.iter().filter(|group| group.has_container()).count())
+    }
 
-            if overlaps {
-                overlaps_counter += 1;
+    fn part2(groups: &Vec<Group>) -> Result<usize, Error> {
+        Ok(groups.iter().filter(|group| group.has_overlap()).count())
+    }
+}
+
+impl Day4 {
+    /// Prints, for each group, which of its members overlap, numbering groups
+    /// by line and members from 1. With `over`, only the groups with more
+    /// than that many overlapping pairs are printed.
+    pub fn write_overlaps(
+        out: &mut impl Write,
+        groups: &[Group],
+        over: Option<usize>,
+    ) -> io::Result<()> {
+        for (index, group) in groups.iter().enumerate() {
+            let members = group.overlapping_members();
+
+            if over.is_some_and(|over| members.len() <= over) {
+                continue;
             }
+
+            let assignments: Vec<String> = group
+                .assignments()
+                .iter()
+                .map(Assignment::to_string)
+                .collect();
+            let overlaps: Vec<String> = members
+                .iter()
+                .map(|(member_a, member_b)| format!("{}&{}", member_a + 1, member_b + 1))
+                .collect();
+
+            writeln!(
+                out,
+                "{:>6} {:<30} {}",
+                index + 1,
+                assignments.join(","),
+                if overlaps.is_empty() {
+                    String::from("none")
+                } else {
+                    overlaps.join(" ")
+                }
+            )?;
         }
 
-        println!("Part 2 - Total overlap pairs: {}", overlaps_counter);
+        Ok(())
     }
 }
 
@@ -91,70 +278,203 @@ mod tests {
 
     #[test]
     fn test_assignment() {
-        let assignment = Assignment::new("2-4");
+        let assignment = Assignment::new("2-4").unwrap();
         assert_eq!(assignment.len(), 3);
 
-        let assignment = Assignment::new("4-5");
+        let assignment = Assignment::new("4-5").unwrap();
         assert_eq!(assignment.len(), 2);
 
-        let assignment = Assignment::new("6-6");
+        let assignment = Assignment::new("6-6").unwrap();
         assert_eq!(assignment.len(), 1);
     }
 
     #[test]
     fn test_assignment_fully_contains() {
-        let assignment_a = Assignment::new("2-4");
-        let assignment_b = Assignment::new("6-8");
+        let assignment_a = Assignment::new("2-4").unwrap();
+        let assignment_b = Assignment::new("6-8").unwrap();
 
         assert!(!assignment_a.fully_contains(&assignment_b));
 
-        let assignment_a = Assignment::new("2-6");
-        let assignment_b = Assignment::new("4-8");
+        let assignment_a = Assignment::new("2-6").unwrap();
+        let assignment_b = Assignment::new("4-8").unwrap();
 
         assert!(!assignment_a.fully_contains(&assignment_b));
 
-        let assignment_a = Assignment::new("2-8");
-        let assignment_b = Assignment::new("3-7");
+        let assignment_a = Assignment::new("2-8").unwrap();
+        let assignment_b = Assignment::new("3-7").unwrap();
 
         assert!(assignment_a.fully_contains(&assignment_b));
         assert!(!assignment_b.fully_contains(&assignment_a));
 
-        let assignment_a = Assignment::new("2-3");
-        let assignment_b = Assignment::new("2-3");
+        let assignment_a = Assignment::new("2-3").unwrap();
+        let assignment_b = Assignment::new("2-3").unwrap();
 
         assert!(assignment_a.fully_contains(&assignment_b));
     }
 
     #[test]
     fn test_assignment_overlaps() {
-        let assignment_a = Assignment::new("2-4");
-        let assignment_b = Assignment::new("6-8");
+        let assignment_a = Assignment::new("2-4").unwrap();
+        let assignment_b = Assignment::new("6-8").unwrap();
 
         assert!(!assignment_a.overlaps(&assignment_b));
 
-        let assignment_a = Assignment::new("2-3");
-        let assignment_b = Assignment::new("4-5");
+        let assignment_a = Assignment::new("2-3").unwrap();
+        let assignment_b = Assignment::new("4-5").unwrap();
 
         assert!(!assignment_a.overlaps(&assignment_b));
 
-        let assignment_a = Assignment::new("5-7");
-        let assignment_b = Assignment::new("7-9");
+        let assignment_a = Assignment::new("5-7").unwrap();
+        let assignment_b = Assignment::new("7-9").unwrap();
 
         assert!(assignment_a.overlaps(&assignment_b));
 
-        let assignment_a = Assignment::new("2-8");
-        let assignment_b = Assignment::new("3-7");
+        let assignment_a = Assignment::new("2-8").unwrap();
+        let assignment_b = Assignment::new("3-7").unwrap();
 
         assert!(assignment_a.overlaps(&assignment_b));
 
-        let assignment_a = Assignment::new("6-6");
-        let assignment_b = Assignment::new("4-6");
+        let assignment_a = Assignment::new("6-6").unwrap();
+        let assignment_b = Assignment::new("4-6").unwrap();
 
         assert!(assignment_a.overlaps(&assignment_b));
 
-        let assignment_a = Assignment::new("2-6");
-        let assignment_b = Assignment::new("4-8");
+        let assignment_a = Assignment::new("2-6").unwrap();
+        let assignment_b = Assignment::new("4-8").unwrap();
 
         assert!(assignment_a.overlaps(&assignment_b));
     }
+
+    #[test]
+    fn test_assignment_algebra() {
+        let assignment = |sections| Assignment::new(sections).unwrap();
+
+        assert_eq!(
+            assignment("2-6").intersection(&assignment("4-8")),
+            Some(assignment("4-6"))
+        );
+        assert_eq!(assignment("2-3").intersection(&assignment("4-5")), None);
+
+        assert_eq!(
+            assignment("2-6").union(&assignment("4-8")),
+            [assignment("2-8")]
+        );
+        assert_eq!(
+            assignment("4-5").union(&assignment("2-3")),
+            [assignment("2-5")]
+        );
+        assert_eq!(
+            assignment("6-8").union(&assignment("2-4")),
+            [assignment("2-4"), assignment("6-8")]
+        );
+
+        assert_eq!(
+            assignment("2-8").difference(&assignment("4-5")),
+            [assignment("2-3"), assignment("6-8")]
+        );
+        assert_eq!(
+            assignment("2-8").difference(&assignment("1-5")),
+            [assignment("6-8")]
+        );
+        assert_eq!(
+            assignment("2-8").difference(&assignment("9-9")),
+            [assignment("2-8")]
+        );
+        assert!(assignment("4-5").difference(&assignment("2-8")).is_empty());
+        assert_eq!(
+            assignment("0-4294967295").difference(&assignment("0-4294967294")),
+            [assignment("4294967295-4294967295")]
+        );
+
+        assert!(assignment("2-3").is_adjacent(&assignment("4-5")));
+        assert!(assignment("4-5").is_adjacent(&assignment("2-3")));
+        assert!(!assignment("2-4").is_adjacent(&assignment("4-5")));
+        assert!(!assignment("2-3").is_adjacent(&assignment("5-6")));
+
+        assert!(assignment("2-4").contains(4));
+        assert!(!assignment("2-4").contains(5));
+        assert_eq!(assignment("0-4294967295").len(), 1 << 32);
+        assert_eq!(Assignment::from_sections(5, 4), None);
+
+        let mut assignments = vec![assignment("4-6"), assignment("2-8"), assignment("2-3")];
+        assignments.sort();
+        assert_eq!(
+            assignments,
+            [assignment("2-3"), assignment("2-8"), assignment("4-6")]
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        let groups = Day4::parse("2-8,3-7,4-4\n2-4,3-8,4-5\n1-2,3-4,5-6,2-3\n1-1,3-3,5-5").unwrap();
+
+        let containers: Vec<bool> = groups.iter().map(Group::has_container).collect();
+        assert_eq!(containers, [true, false, false, false]);
+
+        let overlaps: Vec<bool> = groups.iter().map(Group::has_overlap).collect();
+        assert_eq!(overlaps, [true, true, true, false]);
+
+        assert_eq!(groups[0].overlapping_members(), [(0, 1), (0, 2), (1, 2)]);
+        assert_eq!(groups[1].overlapping_members(), [(0, 1), (0, 2), (1, 2)]);
+        assert_eq!(groups[2].overlapping_members(), [(0, 3), (1, 3)]);
+        assert!(groups[3].overlapping_members().is_empty());
+
+        assert_eq!(Day4::part1(&groups).unwrap(), 1);
+        assert_eq!(Day4::part2(&groups).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_write_overlaps() {
+        let groups = Day4::parse("5-7,7-9,1-2\n2-4,6-8\n1-9,2-3,3-4").unwrap();
+
+        let mut out = Vec::new();
+        Day4::write_overlaps(&mut out, &groups[..2], None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "     1 5-7,7-9,1-2                    1&2\n     2 2-4,6-8                        none\n"
+        );
+
+        let mut out = Vec::new();
+        Day4::write_overlaps(&mut out, &groups, Some(1)).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "     3 1-9,2-3,3-4                    1&2 1&3 2&3\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_assignment() {
+        assert_eq!(Assignment::new("2-x").err().unwrap().text, "x");
+        assert_eq!(Assignment::new("24").err().unwrap().text, "24");
+        assert_eq!(Assignment::new("4-2").err().unwrap().text, "4-2");
+
+        match Day4::parse("2-4,6-8\n2-3,4--5").err().unwrap() {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 7);
+                assert_eq!(text, "-5");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        match Day4::parse("2-4,6-8,7-x").err().unwrap() {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(line, 1);
+                assert_eq!(column, 11);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        match Day4::parse("2-4").err().unwrap() {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(line, 1);
+                assert_eq!(column, 4);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
 }
@@ -1,11 +1,13 @@
-use std::fs;
+use crate::error::{Error, Invalid, Line};
+use crate::solution::Solution;
 
-struct CratesPort {
+#[derive(Clone)]
+pub struct CratesPort {
     stacks: Vec<Vec<Crate>>,
 }
 
 impl CratesPort {
-    fn new(number_stacks: usize) -> Self {
+    pub fn new(number_stacks: usize) -> Self {
         let mut port = Self {
             stacks: Vec::with_capacity(number_stacks),
         };
@@ -17,31 +19,49 @@ impl CratesPort {
         port
     }
 
-    fn add_crate(&mut self, item: Crate, stack_index: usize) {
+    pub fn add_crate(&mut self, item: Crate, stack_index: usize) {
         self.stacks[stack_index].push(item);
     }
 
-    fn individual_movement(&mut self, action: Action) {
-        for _ in 1..=action.amount {
-            let item = self.stacks[action.origin].pop().unwrap();
+    pub fn individual_movement(&mut self, action: Action) -> Result<(), Error> {
+        let crates = self.take_crates(&action)?;
+
+        for item in crates.into_iter().rev() {
             self.stacks[action.destiny].push(item)
         }
+
+        Ok(())
     }
 
-    fn group_movement(&mut self, action: Action) {
-        let mut crates = Vec::new();
+    pub fn group_movement(&mut self, action: Action) -> Result<(), Error> {
+        let crates = self.take_crates(&action)?;
 
-        for _ in 1..=action.amount {
-            let item = self.stacks[action.origin].pop().unwrap();
-            crates.push(item);
+        for item in crates {
+            self.stacks[action.destiny].push(item)
         }
 
-        for item in crates.into_iter().rev() {
-            self.stacks[action.destiny].push(item)
+        Ok(())
+    }
+
+    fn take_crates(&mut self, action: &Action) -> Result<Vec<Crate>, Error> {
+        let stack = &mut self.stacks[action.origin];
+
+        if stack.len() < action.amount {
+            return Err(Error::solve(
+                Day5::DAY,
+                format!(
+                    "cannot move {} crates from stack {} holding {}",
+                    action.amount,
+                    action.origin + 1,
+                    stack.len()
+                ),
+            ));
         }
+
+        Ok(stack.split_off(stack.len() - action.amount))
     }
 
-    fn get_upper_crates_msg(&self) -> String {
+    pub fn get_upper_crates_msg(&self) -> String {
         let mut msg = String::new();
 
         for stack in &self.stacks {
@@ -55,25 +75,26 @@ impl CratesPort {
     }
 }
 
-struct Crate {
+#[derive(Clone)]
+pub struct Crate {
     id: String,
 }
 
 impl Crate {
-    fn new(id: String) -> Self {
+    pub fn new(id: String) -> Self {
         Self { id }
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Action {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Action {
     origin: usize,
     destiny: usize,
     amount: usize,
 }
 
 impl Action {
-    fn new(origin: usize, destiny: usize, amount: usize) -> Self {
+    pub fn new(origin: usize, destiny: usize, amount: usize) -> Self {
         // Puzzle inputs start from 1
         Self {
             origin: origin - 1,
@@ -83,58 +104,75 @@ impl Action {
     }
 }
 
-pub struct Day5 {}
+pub struct Rearrangement {
+    crates_port: CratesPort,
+    actions: Vec<Action>,
+}
 
-impl Day5 {
-    pub fn run() {
-        let filename = "src/day5/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl Rearrangement {
+    fn upper_crates_msg(
+        &self,
+        movement: fn(&mut CratesPort, Action) -> Result<(), Error>,
+    ) -> Result<String, Error> {
+        let mut crates_port = self.crates_port.clone();
 
-        // Part 1
-        let number_stacks = Self::get_number_stacks(&data);
-        let mut crates_port = CratesPort::new(number_stacks);
+        for action in &self.actions {
+            movement(&mut crates_port, *action)?;
+        }
 
-        let mut crates = Vec::new();
-        let mut actions = Vec::new();
+        Ok(crates_port.get_upper_crates_msg())
+    }
+}
 
-        for line in data.split('\n') {
-            if line.contains('[') {
-                let crates_data = Self::parse_crates_line(line);
-                crates.push(crates_data);
-            } else if line.contains("move") {
-                let action = Self::parse_action_line(line);
-                actions.push(action);
-            } else {
-                continue;
-            }
-        }
+pub struct Day5 {}
 
-        for (index, item) in crates.into_iter().rev().flatten() {
-            crates_port.add_crate(item, index)
-        }
+impl Solution for Day5 {
+    const DAY: u8 = 5;
 
-        for action in actions {
-            crates_port.individual_movement(action);
-        }
+    type Input = Rearrangement;
+    type Part1 = String;
+    type Part2 = String;
 
-        println!(
-            "Part 1 - Msg from crates on top: {}",
-            crates_port.get_upper_crates_msg()
-        );
+    const PART1_LABEL: &'static str = "Msg from crates on top";
+    const PART2_LABEL: &'static str = "Msg from crates on top";
 
-        // Part 2
-        let number_stacks = Self::get_number_stacks(&data);
+    fn parse(data: &str) -> Result<Rearrangement, Error> {
+        let number_stacks = Self::get_number_stacks(data);
         let mut crates_port = CratesPort::new(number_stacks);
 
         let mut crates = Vec::new();
         let mut actions = Vec::new();
 
-        for line in data.split('\n') {
-            if line.contains('[') {
-                let crates_data = Self::parse_crates_line(line);
+        for line in Line::enumerate(Self::DAY, data) {
+            let text = line.text();
+
+            if text.contains('[') {
+                let crates_data = Self::parse_crates_line(text);
+
+                if let Some((index, _)) = crates_data
+                    .iter()
+                    .find(|(index, _)| *index >= number_stacks)
+                {
+                    let (offset, item) = text.char_indices().nth(1 + 4 * index).unwrap_or_default();
+                    let item = &text[offset..offset + item.len_utf8()];
+                    return Err(
+                        line.error(item, format!("there are only {} stacks", number_stacks))
+                    );
+                }
+
                 crates.push(crates_data);
-            } else if line.contains("move") {
-                let action = Self::parse_action_line(line);
+            } else if text.contains("move") {
+                let action =
+                    Self::parse_action_line(text).map_err(|invalid| line.invalid(invalid))?;
+
+                for stack in [action.origin, action.destiny] {
+                    if stack >= number_stacks {
+                        return Err(
+                            line.error(text, format!("there are only {} stacks", number_stacks))
+                        );
+                    }
+                }
+
                 actions.push(action);
             } else {
                 continue;
@@ -145,16 +183,22 @@ impl Day5 {
             crates_port.add_crate(item, index)
         }
 
-        for action in actions {
-            crates_port.group_movement(action);
-        }
+        Ok(Rearrangement {
+            crates_port,
+            actions,
+        })
+    }
 
-        println!(
-            "Part 2 - Msg from crates on top: {}",
-            crates_port.get_upper_crates_msg()
-        );
+    fn part1(rearrangement: &Rearrangement) -> Result<String, Error> {
+        rearrangement.upper_crates_msg(CratesPort::individual_movement)
     }
 
+    fn part2(rearrangement: &Rearrangement) -> Result<String, Error> {
+        rearrangement.upper_crates_msg(CratesPort::group_movement)
+    }
+}
+
+impl Day5 {
     fn parse_crates_line(line: &str) -> Vec<(usize, Crate)> {
         line.chars()
             .skip(1)
@@ -165,21 +209,40 @@ impl Day5 {
             .collect()
     }
 
-    fn parse_action_line(line: &str) -> Action {
-        let action: Vec<usize> = line
-                    .replace("move ", "")
-                    .replace(" from ", " ")
-                    .replace(" to ", " ")
-                    .split_whitespace()
-                    .take(3)
-                    .map(|x| x.parse::<usize>().unwrap())
-                    .collect();
-
-        Action::new(action[1], action[2], action[0])
+    fn parse_action_line(line: &str) -> Result<Action, Invalid<'_>> {
+        let mut action = [0; 3];
+        let mut tokens = line.split_whitespace();
+
+        for (keyword, value) in ["move", "from", "to"].into_iter().zip(action.iter_mut()) {
+            match tokens.next() {
+                Some(token) if token == keyword => {}
+                Some(token) => return Err(Invalid::new(token, format!("expected '{}'", keyword))),
+                None => {
+                    return Err(Invalid::new(
+                        &line[line.len()..],
+                        format!("expected '{}'", keyword),
+                    ))
+                }
+            }
+
+            let number = tokens
+                .next()
+                .ok_or_else(|| Invalid::new(&line[line.len()..], "expected a number"))?;
+            *value = number
+                .parse::<usize>()
+                .map_err(|_| Invalid::new(number, "invalid number"))?;
+
+            // Stacks are numbered from 1
+            if keyword != "move" && *value == 0 {
+                return Err(Invalid::new(number, "invalid stack"));
+            }
+        }
+
+        Ok(Action::new(action[1], action[2], action[0]))
     }
 
     fn get_number_stacks(data: &str) -> usize {
-        let first_line_len = data.split('\n').next().unwrap().len();
+        let first_line_len = data.split('\n').next().unwrap_or_default().len();
         (first_line_len + 1) / 4
     }
 }
@@ -206,12 +269,12 @@ mod tests {
     #[test]
     fn test_parse_action() {
         let line = "move 1 from 2 to 1";
-        let action = Day5::parse_action_line(line);
+        let action = Day5::parse_action_line(line).unwrap();
 
         assert_eq!(action, Action::new(2, 1, 1));
         
         let line = "move 11 from 4 to 1";
-        let action = Day5::parse_action_line(line);
+        let action = Day5::parse_action_line(line).unwrap();
         assert_eq!(action, Action::new(4, 1, 11));
     }
 
@@ -288,7 +351,7 @@ mod tests {
         }
 
         let action = Action::new(2, 3, 2);
-        port.individual_movement(action);
+        port.individual_movement(action).unwrap();
 
         assert_eq!(port.stacks[0].len(), 1);
         assert_eq!(port.stacks[1].len(), 0);
@@ -298,7 +361,7 @@ mod tests {
         assert_eq!(port.stacks[2].first().unwrap().id, "Z");
 
         let action = Action::new(1, 3, 1);
-        port.individual_movement(action);
+        port.individual_movement(action).unwrap();
 
         assert_eq!(port.stacks[0].len(), 0);
         assert_eq!(port.stacks[1].len(), 0);
@@ -338,7 +401,7 @@ mod tests {
         }
 
         let action = Action::new(2, 3, 2);
-        port.group_movement(action);
+        port.group_movement(action).unwrap();
 
         assert_eq!(port.stacks[0].len(), 1);
         assert_eq!(port.stacks[1].len(), 0);
@@ -348,7 +411,7 @@ mod tests {
         assert_eq!(port.stacks[2].first().unwrap().id, "Z");
 
         let action = Action::new(1, 3, 1);
-        port.group_movement(action);
+        port.group_movement(action).unwrap();
 
         assert_eq!(port.stacks[0].len(), 0);
         assert_eq!(port.stacks[1].len(), 0);
@@ -356,4 +419,34 @@ mod tests {
         assert_eq!(port.stacks[2].last().unwrap().id, "N");
         assert_eq!(port.stacks[2].first().unwrap().id, "Z");
     }
+
+    #[test]
+    fn test_invalid_action() {
+        let invalid = Day5::parse_action_line("move 1 form 2 to 1").err().unwrap();
+        assert_eq!(invalid.text, "form");
+
+        let invalid = Day5::parse_action_line("move 1 from 0 to 1").err().unwrap();
+        assert_eq!(invalid.text, "0");
+
+        let invalid = Day5::parse_action_line("move x from 2 to 1").err().unwrap();
+        assert_eq!(invalid.text, "x");
+
+        match Day5::parse("[A]    \n 1   2 \n\nmove 1 from 1 to 3")
+            .err()
+            .unwrap()
+        {
+            Error::Parse { line, .. } => assert_eq!(line, 4),
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_movement_from_empty_stack() {
+        let mut port = CratesPort::new(2);
+        port.add_crate(Crate::new(String::from("A")), 0);
+
+        assert!(port.individual_movement(Action::new(1, 2, 2)).is_err());
+        assert!(port.group_movement(Action::new(2, 1, 1)).is_err());
+        assert!(port.group_movement(Action::new(1, 2, 1)).is_ok());
+    }
 }
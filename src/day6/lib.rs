@@ -1,30 +1,42 @@
-use std::{fs, collections::HashSet};
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::solution::Solution;
 
 pub struct Day6 {}
 
-impl Day6 {
-    pub fn run() {
-        let filename = "src/day6/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl Solution for Day6 {
+    const DAY: u8 = 6;
 
-        // Part 1
-        let marker = Self::get_packet_marker(&data);
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        println!("Part 1 - Packet marker is: {}", marker);
+    const PART1_LABEL: &'static str = "Packet marker is";
+    const PART2_LABEL: &'static str = "Message index is";
 
-        // Part 2
-        let marker = Self::get_msg_marker(&data);
+    fn parse(data: &str) -> Result<String, Error> {
+        Ok(String::from(data.trim_end()))
+    }
 
-        println!("Part 2 - Message index is: {}", marker);
+    fn part1(data: &String) -> Result<usize, Error> {
+        Self::get_packet_marker(data)
+            .ok_or_else(|| Error::solve(Self::DAY, "no packet marker found"))
     }
 
-    fn get_marker(data: &str, group_size: usize) -> usize {
-        let mut marker = 0;
+    fn part2(data: &String) -> Result<usize, Error> {
+        Self::get_msg_marker(data).ok_or_else(|| Error::solve(Self::DAY, "no message marker found"))
+    }
+}
 
-        for i in 0..(data.len() - (group_size - 1)) {
+impl Day6 {
+    pub fn get_marker(data: &str, group_size: usize) -> Option<usize> {
+        let mut marker = None;
+
+        for i in 0..(data.len() + 1).saturating_sub(group_size) {
             let group: HashSet<char> = data.chars().skip(i).take(group_size).collect();
             if group.len() == group_size {
-                marker = i + group_size;
+                marker = Some(i + group_size);
                 break;
             }
         }
@@ -32,11 +44,11 @@ impl Day6 {
         marker
     }
 
-    fn get_packet_marker(data: &str) -> usize {
+    pub fn get_packet_marker(data: &str) -> Option<usize> {
         Self::get_marker(data, 4)
     }
 
-    fn get_msg_marker(data: &str) -> usize {
+    pub fn get_msg_marker(data: &str) -> Option<usize> {
         Self::get_marker(data, 14)
     }
 
@@ -50,45 +62,53 @@ mod tests {
     fn test_packet_marker() {
         let data = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
         let marker = Day6::get_packet_marker(data);
-        assert_eq!(marker, 7);
+        assert_eq!(marker, Some(7));
 
         let data = "bvwbjplbgvbhsrlpgdmjqwftvncz";
         let marker = Day6::get_packet_marker(data);
-        assert_eq!(marker, 5);
+        assert_eq!(marker, Some(5));
 
         let data = "nppdvjthqldpwncqszvftbrmjlhg";
         let marker = Day6::get_packet_marker(data);
-        assert_eq!(marker, 6);
+        assert_eq!(marker, Some(6));
 
         let data = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
         let marker = Day6::get_packet_marker(data);
-        assert_eq!(marker, 10);
+        assert_eq!(marker, Some(10));
 
         let data = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
         let marker = Day6::get_packet_marker(data);
-        assert_eq!(marker, 11);
+        assert_eq!(marker, Some(11));
     }
 
     #[test]
     fn test_msg_marker() {
         let data = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
         let marker = Day6::get_msg_marker(data);
-        assert_eq!(marker, 19);
+        assert_eq!(marker, Some(19));
 
         let data = "bvwbjplbgvbhsrlpgdmjqwftvncz";
         let marker = Day6::get_msg_marker(data);
-        assert_eq!(marker, 23);
+        assert_eq!(marker, Some(23));
 
         let data = "nppdvjthqldpwncqszvftbrmjlhg";
         let marker = Day6::get_msg_marker(data);
-        assert_eq!(marker, 23);
+        assert_eq!(marker, Some(23));
 
         let data = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
         let marker = Day6::get_msg_marker(data);
-        assert_eq!(marker, 29);
+        assert_eq!(marker, Some(29));
 
         let data = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
         let marker = Day6::get_msg_marker(data);
-        assert_eq!(marker, 26);
+        assert_eq!(marker, Some(26));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(Day6::get_packet_marker("abab"), None);
+        assert_eq!(Day6::get_packet_marker("abc"), None);
+        assert_eq!(Day6::get_packet_marker("abcd"), Some(4));
+        assert_eq!(Day6::get_msg_marker(""), None);
+    }
+}
@@ -1,13 +1,14 @@
-use std::fs;
+use crate::error::{Error, Invalid, Line};
+use crate::solution::Solution;
 
-type FileSystemItemId = usize;
-struct FileSystem {
+pub type FileSystemItemId = usize;
+pub struct FileSystem {
     data: Vec<FileSystemItem>,
     current_dir_id: FileSystemItemId,
 }
 
 impl FileSystem {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self { 
             data: vec![
                 FileSystemItem {
@@ -21,13 +22,15 @@ impl FileSystem {
         }
     }
 
-    fn change_dir(&mut self, dir: &str) {
+    pub fn change_dir<'a>(&mut self, dir: &'a str) -> Result<(), Invalid<'a>> {
         let current_dir = self.get_current_dir();
 
         // Pop up folder
         if dir == ".." {
-            self.current_dir_id = current_dir.parent.expect("There is no parent");
-            return;
+            self.current_dir_id = current_dir
+                .parent
+                .ok_or_else(|| Invalid::new(dir, "there is no parent"))?;
+            return Ok(());
         }
 
         // Move to folder
@@ -47,12 +50,13 @@ impl FileSystem {
         match dir_id {
             Some(dir_id) => {
                 self.current_dir_id = dir_id;
+                Ok(())
             }
-            None => panic!("Folder not found in the current dir"),
+            None => Err(Invalid::new(dir, "folder not found in the current dir")),
         }
     }
 
-    fn add_file(&mut self, name: &str, size: u32) -> FileSystemItemId {
+    pub fn add_file(&mut self, name: &str, size: u32) -> FileSystemItemId {
         let new_file = FileSystemItem {
             name: String::from(name),
             parent: Some(self.current_dir_id),
@@ -78,7 +82,7 @@ impl FileSystem {
         new_file_id
     }
 
-    fn add_folder(&mut self, name: &str) -> FileSystemItemId {
+    pub fn add_folder(&mut self, name: &str) -> FileSystemItemId {
         let new_folder = FileSystemItem {
             name: String::from(name),
             parent: Some(self.current_dir_id),
@@ -114,7 +118,7 @@ impl FileSystem {
         self.data.get_mut(id).expect("Dir id is not valid")
     }
 
-    fn simple_total_size(&self, threshold: u32) -> u32 {
+    pub fn simple_total_size(&self, threshold: u32) -> u32 {
         self.data
         .iter()
         .filter(|item| item.children.is_some())
@@ -122,11 +126,11 @@ impl FileSystem {
         .fold(0, |acc, item| acc + item.size )
     }
 
-    fn folder_removal_selection(&self, disk_space: u32, space_needed: u32) -> u32 {
+    pub fn folder_removal_selection(&self, disk_space: u32, space_needed: u32) -> Option<u32> {
         // Root dir
         let used_space = self.data[0].size;
-        let free_space = disk_space - used_space;
-        let missing_space = space_needed - free_space;
+        let free_space = disk_space.checked_sub(used_space)?;
+        let missing_space = space_needed.saturating_sub(free_space);
 
         self.data
         .iter()
@@ -134,7 +138,12 @@ impl FileSystem {
         .map(|dir| dir.size)
         .filter(|dir| dir >= &missing_space)
         .min()
-        .unwrap()
+    }
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -147,40 +156,64 @@ struct FileSystemItem {
 
 pub struct Day7 {}
 
-impl Day7 {
-    pub fn run() {
-        let filename = "src/day7/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl Solution for Day7 {
+    const DAY: u8 = 7;
 
-        // Part 1
+    type Input = FileSystem;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const PART1_LABEL: &'static str = "Total size is";
+    const PART2_LABEL: &'static str = "Size of folder to remove";
+
+    fn parse(data: &str) -> Result<FileSystem, Error> {
         let mut filesystem = FileSystem::new();
 
         // Dir '/' is already the entry point
-        for line in data.lines().skip(1) {
-            if line.starts_with("$ cd") {
-                let dir = line.split_whitespace().nth(2).unwrap();
-                
-                filesystem.change_dir(dir);
-            } else if line == "$ ls" {
+        for line in Line::enumerate(Self::DAY, data).skip(1) {
+            let text = line.text();
+
+            if text.starts_with("$ cd") {
+                let dir = text
+                    .split_whitespace()
+                    .nth(2)
+                    .ok_or_else(|| line.missing("missing folder name"))?;
+
+                filesystem
+                    .change_dir(dir)
+                    .map_err(|invalid| line.invalid(invalid))?;
+            } else if text == "$ ls" {
                 continue;
-            } else if line.starts_with("dir") {
-                let folder_name = line.chars().skip(4).collect::<String>();
-                
-                filesystem.add_folder(&folder_name);
+            } else if text.starts_with('$') {
+                return Err(line.error(text, "unknown command"));
+            } else if let Some(folder_name) = text.strip_prefix("dir ") {
+                filesystem.add_folder(folder_name);
             } else {
-                let data: Vec<&str> = line.split_whitespace().take(2).collect();
-                let file_size = data[0].parse().unwrap();
-                let file_name = data[1];
+                let mut data = text.split_whitespace();
+
+                let file_size = data.next().unwrap_or_default();
+                let file_size = file_size
+                    .parse()
+                    .map_err(|_| line.error(file_size, "invalid file size"))?;
+                let file_name = data
+                    .next()
+                    .ok_or_else(|| line.missing("missing file name"))?;
 
                 filesystem.add_file(file_name, file_size);
             }
         }
 
-        println!("Part 1 - Total size is: {}", filesystem.simple_total_size(100_000));
+        Ok(filesystem)
+    }
 
-        println!("Part 2 - Size of folder to remove: {}", filesystem.folder_removal_selection(70_000_000, 30_000_000));
+    fn part1(filesystem: &FileSystem) -> Result<u32, Error> {
+        Ok(filesystem.simple_total_size(100_000))
+    }
 
-        // TODO: Go through the folder
+    fn part2(filesystem: &FileSystem) -> Result<u32, Error> {
+        filesystem
+            .folder_removal_selection(70_000_000, 30_000_000)
+            .ok_or_else(|| Error::solve(Self::DAY, "used space exceeds the disk space"))
     }
 }
 
@@ -196,26 +229,26 @@ mod tests {
         filesystem.add_file("c", 8504156);
         filesystem.add_folder("d");
 
-        filesystem.change_dir("a");
+        filesystem.change_dir("a").unwrap();
 
         filesystem.add_folder("e");
         filesystem.add_file("f", 29116);
         filesystem.add_file("g", 2557);
         filesystem.add_file("h.lst", 62596);
 
-        filesystem.change_dir("e");
+        filesystem.change_dir("e").unwrap();
         filesystem.add_file("i", 584);
         
-        filesystem.change_dir("..");
-        filesystem.change_dir("..");
-        filesystem.change_dir("d");
+        filesystem.change_dir("..").unwrap();
+        filesystem.change_dir("..").unwrap();
+        filesystem.change_dir("d").unwrap();
 
         filesystem.add_file("j", 4060174);
         filesystem.add_file("d.log", 8033020);
         filesystem.add_file("d.ext", 5626152);
         filesystem.add_file("k", 7214296);
 
-        filesystem.change_dir("..");
+        filesystem.change_dir("..").unwrap();
 
         filesystem
     }
@@ -225,18 +258,18 @@ mod tests {
         let mut filesystem = generate_filesystem();
         assert_eq!(filesystem.get_current_dir().name, "/");
 
-        filesystem.change_dir("a");
+        filesystem.change_dir("a").unwrap();
         assert_eq!(filesystem.get_current_dir().size, 94853);
 
-        filesystem.change_dir("e");
+        filesystem.change_dir("e").unwrap();
         assert_eq!(filesystem.get_current_dir().size, 584);
-        
-        filesystem.change_dir("..");    
-        filesystem.change_dir("..");
-        filesystem.change_dir("d");
+
+        filesystem.change_dir("..").unwrap();
+        filesystem.change_dir("..").unwrap();
+        filesystem.change_dir("d").unwrap();
         assert_eq!(filesystem.get_current_dir().size, 24933642);
 
-        filesystem.change_dir("..");
+        filesystem.change_dir("..").unwrap();
         assert_eq!(filesystem.get_current_dir().size, 48381165);
     }
 
@@ -249,6 +282,39 @@ mod tests {
     #[test]
     fn test_smallest_free_space() {
         let filesystem = generate_filesystem();
-        assert_eq!(filesystem.folder_removal_selection(70_000_000, 30_000_000), 24933642);
+        assert_eq!(
+            filesystem.folder_removal_selection(70_000_000, 30_000_000),
+            Some(24933642)
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_commands() {
+        let mut filesystem = generate_filesystem();
+        assert_eq!(filesystem.change_dir("z").err().unwrap().text, "z");
+        assert_eq!(filesystem.change_dir("..").err().unwrap().text, "..");
+        assert_eq!(
+            filesystem.folder_removal_selection(40_000_000, 30_000_000),
+            None
+        );
+
+        match Day7::parse("$ cd /\n$ ls\ndir a\n$ cd b").err().unwrap() {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 4);
+                assert_eq!(column, 6);
+                assert_eq!(text, "b");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        match Day7::parse("$ cd /\n$ ls\n12k a.txt").err().unwrap() {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(line, 3);
+                assert_eq!(column, 1);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+}
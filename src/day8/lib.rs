@@ -1,84 +1,53 @@
-use std::{cmp::min, fs};
+use crate::error::{Error, Line};
+use crate::grid::{Direction, Grid, Point};
+use crate::solution::Solution;
 
-struct Forest {
-    map: Vec<Vec<Tree>>,
+#[derive(Default)]
+pub struct Forest {
+    map: Grid<Tree>,
 }
 
 impl Forest {
-    fn new() -> Self {
-        Self { map: Vec::new() }
-    }
-
-    fn add_line(&mut self, line: Vec<Tree>) {
-        self.map.push(line);
-    }
-
-    fn check_visibility(&self, row: usize, col: usize) -> bool {
-        let max_column = self.max_col();
-        let max_row = self.max_row();
-
-        // Perimeter is always visible
-        if row == 0 || row == max_row || col == 0 || col == max_column {
-            return true;
+    pub fn new() -> Self {
+        Self {
+            map: Grid::default(),
         }
-
-        Self::check_visibility_left(self, row, col)
-            || Self::check_visibility_right(self, row, col)
-            || Self::check_visibility_up(self, row, col)
-            || Self::check_visibility_down(self, row, col)
     }
 
-    fn count_trees_view(&self, row: usize, col: usize) -> u32 {
-        Self::count_trees_view_left(self, row, col)
-            * Self::count_trees_view_right(self, row, col)
-            * Self::count_trees_view_up(self, row, col)
-            * Self::count_trees_view_down(self, row, col)
+    pub fn add_line(&mut self, line: Vec<Tree>) {
+        self.map.push_row(line);
     }
 
-    fn check_visibility_left(&self, row: usize, col: usize) -> bool {
-        let height = self.get_tree_height(row, col);
-        (0..col)
-            .map(|i| self.get_tree_height(row,i))
-            .all(|tree_height| tree_height < height)
+    pub fn check_visibility(&self, point: Point) -> bool {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .any(|direction| self.check_visibility_towards(point, direction))
     }
 
-    fn count_trees_view_left(&self, row: usize, col: usize) -> u32 {
-        let height = self.get_tree_height(row, col);
-        
-        let mut counter = 0;
-        for i in (0..col).rev() {
-            let tree_height = self.get_tree_height(row,i);
-            
-            counter += 1;
-
-            if tree_height >= height {
-                break;
-            }
-        }
-
-        counter
+    pub fn count_trees_view(&self, point: Point) -> u32 {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(|direction| self.count_trees_view_towards(point, direction))
+            .product()
     }
 
-    fn check_visibility_right(&self, row: usize, col: usize) -> bool {
-        let height = self.get_tree_height(row, col);
-        let max_column = self.max_col();
+    /// Trees on the edge see out of the forest, as nothing is in between.
+    pub fn check_visibility_towards(&self, point: Point, direction: Direction) -> bool {
+        let height = self.map[point].height;
 
-        (min(col + 1, max_column)..=max_column)
-            .map(|i| self.get_tree_height(row,i))
-            .all(|tree_height| tree_height < height)
+        self.map
+            .ray(point, direction)
+            .all(|(_, tree)| tree.height < height)
     }
 
-    fn count_trees_view_right(&self, row: usize, col: usize) -> u32 {
-        let height = self.get_tree_height(row, col);
-        let max_column = self.max_col();
-        
+    pub fn count_trees_view_towards(&self, point: Point, direction: Direction) -> u32 {
+        let height = self.map[point].height;
+
         let mut counter = 0;
-        for i in min(col + 1, max_column)..=max_column {
-            let tree_height = self.get_tree_height(row,i);
-            
+        for (_, tree) in self.map.ray(point, direction) {
             counter += 1;
-            
-            if tree_height >= height {
+
+            if tree.height >= height {
                 break;
             }
         }
@@ -86,152 +55,91 @@ impl Forest {
         counter
     }
 
-    fn check_visibility_up(&self, row: usize, col: usize) -> bool {
-        let height = self.get_tree_height(row, col);
-        (0..row)
-            .map(|i| self.get_tree_height(i, col))
-            .all(|tree_height| tree_height < height)
-    }
-
-    fn count_trees_view_up(&self, row: usize, col: usize) -> u32 {
-        let height = self.get_tree_height(row, col);
-        
-        let mut counter = 0;
-        for i in (0..row).rev() {
-            let tree_height = self.get_tree_height(i,col);
-            
-            if tree_height <= height {
-                counter += 1;
-
-                if tree_height >= height {
-                    break;
-                }
-            } 
-        }
-
-        counter
-    }
-
-    fn check_visibility_down(&self, row: usize, col: usize) -> bool {
-        let height = self.get_tree_height(row, col);
-        let max_row = self.max_row();
-
-        (min(row + 1, max_row)..=max_row)
-            .map(|i| self.get_tree_height(i, col))
-            .all(|tree_height| tree_height < height)
-    }
-
-    fn count_trees_view_down(&self, row: usize, col: usize) -> u32 {
-        let height = self.get_tree_height(row, col);
-        let max_row = self.max_row();
-        
-        let mut counter = 0;
-        for i in min(row + 1, max_row)..=max_row {
-            let tree_height = self.get_tree_height(i,col);
-            
-            if tree_height <= height {
-                counter += 1;
-
-                if tree_height >= height {
-                    break;
-                }
-            } 
-        }
-
-        counter
+    pub fn count_visible(&self) -> usize {
+        self.map
+            .points()
+            .filter(|point| self.check_visibility(*point))
+            .count()
     }
-    
-    fn count_visible(&self) -> usize {
-        let rows = self.map.len();
-        let cols = self.map[0].len();
-
-        let mut count = 0;
-        for row in 0..rows {
-            for col in 0..cols {
-                if self.check_visibility(row, col) {
-                    count += 1;
-                }
-            }
-        }
 
-        count
+    pub fn count_max_trees_view(&self) -> u32 {
+        self.map
+            .points()
+            .map(|point| self.count_trees_view(point))
+            .max()
+            .unwrap_or(0)
     }
-
-    fn count_max_trees_view(&self) -> u32 {
-        let rows = self.map.len();
-        let cols = self.map[0].len();
-
-        let mut trees_view_result = 0;
-        for row in 0..rows {
-            for col in 0..cols {
-               let trees_view = self.count_trees_view(row, col);
-
-               if trees_view > trees_view_result {
-                trees_view_result = trees_view;
-               }
-            }
-        }
-
-        trees_view_result
-    }
-
-    #[inline]
-    fn max_col(&self) -> usize {
-        self.map[0].len() - 1
-    }
-
-    #[inline]
-    fn max_row(&self) -> usize {
-        self.map.len() - 1
-    }
-
-    #[inline]
-    fn get_tree_height(&self, row: usize, col: usize) -> u32 {
-        self.map[row][col].height
-    }
-
-    
 }
 
-struct Tree {
+pub struct Tree {
     height: u32,
 }
 
 impl Tree {
-    fn new(height: u32) -> Self {
+    pub fn new(height: u32) -> Self {
         Self { height }
     }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
 }
 
 pub struct Day8 {}
 
-impl Day8 {
-    pub fn run() {
-        let filename = "src/day8/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Forest;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    const PART1_LABEL: &'static str = "Number of visible trees";
+    const PART2_LABEL: &'static str = "Max scenic score is";
 
-        // Part 1
+    fn parse(data: &str) -> Result<Forest, Error> {
         let mut forest = Forest::new();
 
-        for line in data.lines() {
-            let forest_line = line
-                .chars()
-                .map(|x| x.to_digit(10).unwrap())
-                .map(Tree::new)
-                .collect::<Vec<Tree>>();
+        for line in Line::enumerate(Self::DAY, data) {
+            let text = line.text();
+
+            let forest_line = text
+                .char_indices()
+                .map(|(index, x)| {
+                    x.to_digit(10).ok_or_else(|| {
+                        line.error(&text[index..index + x.len_utf8()], "invalid tree height")
+                    })
+                })
+                .map(|height| height.map(Tree::new))
+                .collect::<Result<Vec<Tree>, Error>>()?;
+
+            if forest.map.height() > 0 {
+                if forest.map.width() != forest_line.len() {
+                    return Err(line.error(
+                        text,
+                        format!("expected {} trees per line", forest.map.width()),
+                    ));
+                }
+            } else if forest_line.is_empty() {
+                return Err(line.error(text, "empty forest line"));
+            }
 
             forest.add_line(forest_line);
         }
 
-        println!(
-            "Part 1 - Number of visible trees: {}",
-            forest.count_visible()
-        );
+        if forest.map.height() == 0 {
+            return Err(Error::solve(Self::DAY, "the forest is empty"));
+        }
 
-        println!(
-            "Part 2 - Max scenic score is: {}",
-            forest.count_max_trees_view()
-        );
+        Ok(forest)
+    }
+
+    fn part1(forest: &Forest) -> Result<usize, Error> {
+        Ok(forest.count_visible())
+    }
+
+    fn part2(forest: &Forest) -> Result<u32, Error> {
+        Ok(forest.count_max_trees_view())
     }
 }
 
@@ -241,13 +149,14 @@ mod tests {
 
     fn generate_forest() -> Forest {
         Forest {
-            map: vec![
+            map: Grid::from_rows(vec![
                 vec![Tree::new(3), Tree::new(0), Tree::new(3), Tree::new(7), Tree::new(3)],
                 vec![Tree::new(2), Tree::new(5), Tree::new(5), Tree::new(1), Tree::new(2)],
                 vec![Tree::new(6), Tree::new(5), Tree::new(3), Tree::new(3), Tree::new(2)],
                 vec![Tree::new(3), Tree::new(3), Tree::new(5), Tree::new(4), Tree::new(9)],
                 vec![Tree::new(3), Tree::new(5), Tree::new(3), Tree::new(9), Tree::new(0)],
-            ],
+            ])
+            .unwrap(),
         }
     }
 
@@ -256,51 +165,99 @@ mod tests {
         let forest = generate_forest();
         
         // Check inner
-        assert!(forest.check_visibility_left(1, 1));
-        assert!(forest.check_visibility_up(1, 1));
-        assert!(!forest.check_visibility_right(1, 1));
-        assert!(!forest.check_visibility_down(1, 1));
-        assert!(forest.check_visibility(1, 1));
-
-        assert!(!forest.check_visibility_left(2, 2));
-        assert!(!forest.check_visibility_up(2, 2));
-        assert!(!forest.check_visibility_right(2, 2));
-        assert!(!forest.check_visibility_down(2, 2));
-        assert!(!forest.check_visibility(2, 2));
-
-        assert!(!forest.check_visibility(3, 1));
-        assert!(forest.check_visibility(3, 2));
-        assert!(!forest.check_visibility(3, 3));
+        assert!(forest.check_visibility_towards(Point::new(1, 1), Direction::Left));
+        assert!(forest.check_visibility_towards(Point::new(1, 1), Direction::Up));
+        assert!(!forest.check_visibility_towards(Point::new(1, 1), Direction::Right));
+        assert!(!forest.check_visibility_towards(Point::new(1, 1), Direction::Down));
+        assert!(forest.check_visibility(Point::new(1, 1)));
+
+        assert!(!forest.check_visibility_towards(Point::new(2, 2), Direction::Left));
+        assert!(!forest.check_visibility_towards(Point::new(2, 2), Direction::Up));
+        assert!(!forest.check_visibility_towards(Point::new(2, 2), Direction::Right));
+        assert!(!forest.check_visibility_towards(Point::new(2, 2), Direction::Down));
+        assert!(!forest.check_visibility(Point::new(2, 2)));
+
+        assert!(!forest.check_visibility(Point::new(1, 3)));
+        assert!(forest.check_visibility(Point::new(2, 3)));
+        assert!(!forest.check_visibility(Point::new(3, 3)));
 
         // Check edges
-        assert!(forest.check_visibility(0, 0));
-        assert!(forest.check_visibility(0, 4));
-        assert!(forest.check_visibility(4, 0));
-        assert!(forest.check_visibility(4, 4));
+        assert!(forest.check_visibility(Point::new(0, 0)));
+        assert!(forest.check_visibility(Point::new(4, 0)));
+        assert!(forest.check_visibility(Point::new(0, 4)));
+        assert!(forest.check_visibility(Point::new(4, 4)));
 
         // Check perimeter
-        assert!(forest.check_visibility(0, 2));
-        assert!(forest.check_visibility(3, 0));
-        assert!(forest.check_visibility(2, 4));
-        assert!(forest.check_visibility(4, 3));
+        assert!(forest.check_visibility(Point::new(2, 0)));
+        assert!(forest.check_visibility(Point::new(0, 3)));
+        assert!(forest.check_visibility(Point::new(4, 2)));
+        assert!(forest.check_visibility(Point::new(3, 4)));
     }
 
     #[test]
     fn test_count_view() {
         let forest = generate_forest();
-        
-        assert_eq!(forest.count_trees_view_left(1, 2), 1);
-        assert_eq!(forest.count_trees_view_up(1, 2), 1);
-        assert_eq!(forest.count_trees_view_right(1, 2), 2);
-        assert_eq!(forest.count_trees_view_down(1, 2), 2);
-        assert_eq!(forest.count_trees_view(1, 2), 4);
-
-        assert_eq!(forest.count_trees_view_left(3, 2), 2);
-        assert_eq!(forest.count_trees_view_up(3, 2), 2);
-        assert_eq!(forest.count_trees_view_right(3, 2), 2);
-        assert_eq!(forest.count_trees_view_down(3, 2), 1);
-        assert_eq!(forest.count_trees_view(3, 2), 8);
+
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 1), Direction::Left),
+            1
+        );
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 1), Direction::Up),
+            1
+        );
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 1), Direction::Right),
+            2
+        );
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 1), Direction::Down),
+            2
+        );
+        assert_eq!(forest.count_trees_view(Point::new(2, 1)), 4);
+
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 3), Direction::Left),
+            2
+        );
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 3), Direction::Up),
+            2
+        );
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 3), Direction::Right),
+            2
+        );
+        assert_eq!(
+            forest.count_trees_view_towards(Point::new(2, 3), Direction::Down),
+            1
+        );
+        assert_eq!(forest.count_trees_view(Point::new(2, 3)), 8);
 
        
     }
+
+    #[test]
+    fn test_invalid_forest() {
+        match Day8::parse("303\n2a5").err().unwrap() {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 2);
+                assert_eq!(text, "a");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        match Day8::parse("303\n25").err().unwrap() {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 1);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        assert!(Day8::parse("").is_err());
+    }
 }
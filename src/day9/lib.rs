@@ -1,139 +1,128 @@
-use std::{fs, collections::HashSet};
+use std::collections::HashSet;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct Knot {
-    x: i32,
-    y: i32,
+use crate::error::{Error, Line};
+use crate::grid::{Direction, Point};
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Knot {
+    position: Point,
 }
 
 impl Knot {
-    fn new(x: i32, y: i32) -> Self {
+    pub fn new(x: i32, y: i32) -> Self {
         Self {
-            x,
-            y
+            position: Point::new(x, y),
         }
     }
 
-    fn is_touching(&self, other: &Self) -> bool {
-        ((self.x - 1)..=(self.x + 1)).contains(&other.x) && ((self.y - 1)..=(self.y + 1)).contains(&other.y)
+    pub fn position(&self) -> Point {
+        self.position
     }
 
-    fn step(&mut self, direction: &Direction) {
-        match direction {
-            Direction::Left => {self.x -= 1},
-            Direction::Up => {self.y += 1},
-            Direction::Right => {self.x += 1},
-            Direction::Down => {self.y -= 1},
-        };
+    pub fn is_touching(&self, other: &Self) -> bool {
+        self.position.chebyshev_distance(other.position) <= 1
     }
 
-    fn follow(&mut self, other: &Self) -> bool {
+    pub fn step(&mut self, direction: Direction) {
+        self.position = self.position.step(direction);
+    }
+
+    pub fn follow(&mut self, other: &Self) -> bool {
         if self.is_touching(other) {
             return false;
         }
 
-        let delta_x = (other.x - self.x).clamp(-1, 1);
-        let delta_y = (other.y - self.y).clamp(-1, 1);
-
-        self.x += delta_x;
-        self.y += delta_y;
+        self.position = self.position + (other.position - self.position).signum();
 
-        return true;
+        true
     }
 }
 
-enum Direction {
-    Left,
-    Up,
-    Right,
-    Down,
-}
+pub struct Day9 {}
 
-impl Direction {
-    fn parse(str: &str) -> Self {
-        match str {
-            "R" => Self::Right,
-            "U" => Self::Up,
-            "L" => Self::Left,
-            "D" => Self::Down,
-            _ => panic!("No valid direction string")
-        }
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<(Direction, u32)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PART1_LABEL: &'static str = "Visited positions";
+    const PART2_LABEL: &'static str = "Visited positions";
+
+    fn parse(data: &str) -> Result<Vec<(Direction, u32)>, Error> {
+        Line::enumerate(Self::DAY, data)
+            .map(|line| {
+                let mut line_split = line.text().split_whitespace();
+                let step_direction = line_split
+                    .next()
+                    .ok_or_else(|| line.missing("missing direction"))?;
+                let n_steps = line_split
+                    .next()
+                    .ok_or_else(|| line.missing("missing number of steps"))?;
+
+                let step_direction = Self::parse_direction(step_direction)
+                    .ok_or_else(|| line.error(step_direction, "invalid direction"))?;
+                let n_steps = n_steps
+                    .parse()
+                    .map_err(|_| line.error(n_steps, "invalid number of steps"))?;
+
+                Ok((step_direction, n_steps))
+            })
+            .collect()
     }
-}
 
+    fn part1(motions: &Vec<(Direction, u32)>) -> Result<usize, Error> {
+        Self::simulate_rope(motions, 2)
+    }
 
-pub struct Day9 {}
+    fn part2(motions: &Vec<(Direction, u32)>) -> Result<usize, Error> {
+        Self::simulate_rope(motions, 10)
+    }
+}
 
 impl Day9 {
-    pub fn run() {
-        let filename = "src/day9/input.txt";
-        let data = fs::read_to_string(filename).unwrap();
-
-        let mut head = Knot::new(0,0);
-        let mut tail = Knot::new(0,0);
+    fn parse_direction(direction: &str) -> Option<Direction> {
+        match direction {
+            "R" => Some(Direction::Right),
+            "U" => Some(Direction::Up),
+            "L" => Some(Direction::Left),
+            "D" => Some(Direction::Down),
+            _ => None,
+        }
+    }
 
-        let mut tail_positions = HashSet::new();
-        tail_positions.insert(tail);
-
-        for line in data.lines() {
-            let mut line_split = line.split_whitespace();
-            let step_direction = line_split.next().unwrap();
-            let n_steps = line_split.next().unwrap();
-
-            let step_direction = Direction::parse(step_direction);
-            let n_steps = n_steps.parse().unwrap();
-
-            for _ in 0..n_steps {
-                head.step(&step_direction);
-                
-                let did_move = tail.follow(&mut head);
-                
-                if did_move {
-                    tail_positions.insert(tail);
-                }
-            }
+    /// Number of positions visited by the tail of a rope of `knots_len`
+    /// knots, which must be at least 2.
+    pub fn simulate_rope(motions: &[(Direction, u32)], knots_len: usize) -> Result<usize, Error> {
+        if knots_len < 2 {
+            return Err(Error::solve(Self::DAY, "a rope needs at least two knots"));
         }
 
-        println!(
-            "Part 1 - Visited positions: {}",
-            tail_positions.len()
-        );
+        let mut knots = vec![Knot::new(0, 0); knots_len];
 
-        const KNOTS_LEN: usize = 10;
-        let mut knots = [Knot::new(0, 0);KNOTS_LEN];
-        
         let mut tail_positions = HashSet::new();
         tail_positions.insert(knots[0]);
-        
-        for line in data.lines() {
-            let mut line_split = line.split_whitespace();
-            let step_direction = line_split.next().unwrap();
-            let n_steps = line_split.next().unwrap();
-
-            let step_direction = Direction::parse(step_direction);
-            let n_steps = n_steps.parse().unwrap();
 
-            for _ in 0..n_steps {
-                
+        for (step_direction, n_steps) in motions {
+            for _ in 0..*n_steps {
                 let head = knots.get_mut(0).unwrap();
-                head.step(&step_direction);
+                head.step(*step_direction);
 
-                for index in 0..KNOTS_LEN-1 {
+                for index in 0..knots_len - 1 {
                     let head = knots[index];
-                    let tail = knots.get_mut(index+1).unwrap();
-                    
+                    let tail = knots.get_mut(index + 1).unwrap();
+
                     let did_move = tail.follow(&head);
-                    if did_move && index == KNOTS_LEN-2 {
+                    if did_move && index == knots_len - 2 {
                         tail_positions.insert(*tail);
                     }
                 }
             }
         }
 
-        println!(
-            "Part 2 - Visited positions: {}",
-            tail_positions.len()
-        );
+        Ok(tail_positions.len())
     }
 }
 
@@ -159,14 +148,13 @@ mod tests {
     #[test]
     fn test_step() {
         let mut knot = Knot::new(-1, 4);
-        knot.step(&Direction::Up);
-        knot.step(&Direction::Up);
-        knot.step(&Direction::Right);
-        knot.step(&Direction::Down);
-        knot.step(&Direction::Left);
-        knot.step(&Direction::Left);
-        assert_eq!(knot.x, -2);
-        assert_eq!(knot.y, 5);
+        knot.step(Direction::Up);
+        knot.step(Direction::Up);
+        knot.step(Direction::Right);
+        knot.step(Direction::Down);
+        knot.step(Direction::Left);
+        knot.step(Direction::Left);
+        assert_eq!(knot.position, Point::new(-2, 3));
     }
 
     #[test]
@@ -174,19 +162,47 @@ mod tests {
         let mut knot_a = Knot::new(0, 0);
         let mut knot_b = Knot::new(0, 0);
 
-        knot_b.step(&Direction::Up);
-        assert!(!knot_a.follow(&mut knot_b));
-        assert_eq!(knot_a.x, 0);
-        assert_eq!(knot_a.y, 0);
+        knot_b.step(Direction::Up);
+        assert!(!knot_a.follow(&knot_b));
+        assert_eq!(knot_a.position, Point::new(0, 0));
 
-        knot_b.step(&Direction::Right);
-        assert!(!knot_a.follow(&mut knot_b));
-        assert_eq!(knot_a.x, 0);
-        assert_eq!(knot_a.y, 0);
+        knot_b.step(Direction::Right);
+        assert!(!knot_a.follow(&knot_b));
+        assert_eq!(knot_a.position, Point::new(0, 0));
 
-        knot_b.step(&Direction::Up);
-        assert!(knot_a.follow(&mut knot_b));
-        assert_eq!(knot_a.x, 1);
-        assert_eq!(knot_a.y, 1);
+        knot_b.step(Direction::Up);
+        assert!(knot_a.follow(&knot_b));
+        assert_eq!(knot_a.position, Point::new(1, -1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_motion() {
+        match Day9::parse("R 4\nX 2").err().unwrap() {
+            Error::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 1);
+                assert_eq!(text, "X");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+
+        match Day9::parse("R 4\nU -2").err().unwrap() {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(line, 2);
+                assert_eq!(column, 3);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_short_rope() {
+        let motions = Day9::parse("R 4\nU 4").unwrap();
+
+        assert!(Day9::simulate_rope(&motions, 0).is_err());
+        assert!(Day9::simulate_rope(&motions, 1).is_err());
+        assert_eq!(Day9::simulate_rope(&motions, 2).unwrap(), 7);
+    }
+}
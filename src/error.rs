@@ -0,0 +1,306 @@
+use std::{error, fmt, io};
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        input: String,
+        source: io::Error,
+    },
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        context: String,
+        reason: String,
+    },
+    Solve {
+        day: u8,
+        reason: String,
+    },
+    Overflow {
+        day: u8,
+        item: &'static str,
+        index: usize,
+    },
+    Config {
+        path: String,
+        line: usize,
+        reason: String,
+    },
+}
+
+impl Error {
+    pub fn io(input: &str, source: io::Error) -> Self {
+        Self::Io {
+            input: String::from(input),
+            source,
+        }
+    }
+
+    pub fn solve(day: u8, reason: impl Into<String>) -> Self {
+        Self::Solve {
+            day,
+            reason: reason.into(),
+        }
+    }
+
+    /// An arithmetic result too large for its type, caused by the `item`
+    /// (elf, rucksack, ...) at the 0-based `index`.
+    pub fn overflow(day: u8, item: &'static str, index: usize) -> Self {
+        Self::Overflow { day, item, index }
+    }
+
+    /// An error in one of the tool's own files (baselines, answers, ...),
+    /// as opposed to puzzle input.
+    pub fn config(path: &str, line: usize, reason: impl Into<String>) -> Self {
+        Self::Config {
+            path: String::from(path),
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { input, source } => write!(f, "{}: {}", input, source),
+            Self::Parse {
+                day,
+                line,
+                column,
+                text,
+                context,
+                reason,
+            } => {
+                writeln!(
+                    f,
+                    "day {}, line {}, column {}: {} {:?}",
+                    day, line, column, reason, text
+                )?;
+                writeln!(f, "    {}", context)?;
+                write!(f, "    {}^", " ".repeat(column - 1))
+            }
+            Self::Solve { day, reason } => write!(f, "day {}: {}", day, reason),
+            Self::Overflow { day, item, index } => {
+                write!(f, "day {}: arithmetic overflow at {} {}", day, item, index)
+            }
+            Self::Config { path, line, reason } => write!(f, "{}:{}: {}", path, line, reason),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in a fragment of a line, before the line it belongs to is
+/// known. `text` must be a slice of that line so its column can be recovered.
+#[derive(Debug, PartialEq)]
+pub struct Invalid<'a> {
+    pub text: &'a str,
+    pub reason: String,
+}
+
+impl<'a> Invalid<'a> {
+    pub fn new(text: &'a str, reason: impl Into<String>) -> Self {
+        Self {
+            text,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A single line of puzzle input, used to point parse errors at the
+/// offending text.
+pub struct Line<'a> {
+    day: u8,
+    number: usize,
+    text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// `number` is 1-based, as shown in an editor.
+    pub fn new(day: u8, number: usize, text: &'a str) -> Self {
+        Self { day, number, text }
+    }
+
+    pub fn enumerate(day: u8, data: &'a str) -> impl Iterator<Item = Line<'a>> {
+        data.lines()
+            .enumerate()
+            .map(move |(index, text)| Self::new(day, index + 1, text))
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// Builds a parse error pointing at `token`, which should be a slice of
+    /// this line. Anything else points just past the end of the line.
+    pub fn error(&self, token: &str, reason: impl Into<String>) -> Error {
+        Error::Parse {
+            day: self.day,
+            line: self.number,
+            column: self.column(token),
+            text: String::from(token),
+            context: String::from(self.text),
+            reason: reason.into(),
+        }
+    }
+
+    /// Builds a parse error for a field that is absent from this line.
+    pub fn missing(&self, reason: impl Into<String>) -> Error {
+        self.error(&self.text[self.text.len()..], reason)
+    }
+
+    pub fn invalid(&self, invalid: Invalid<'_>) -> Error {
+        self.error(invalid.text, invalid.reason)
+    }
+
+    fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            self.text.chars().count() + 1
+        }
+    }
+}
+
+/// A rule of one of the tool's own `key: value` files, where blank lines and
+/// `#` comments are skipped. Such a file may also take a leading rule, which
+/// replaces the defaults that the other rules then override.
+pub struct ConfigLine<'a> {
+    path: &'a str,
+    number: usize,
+    first: bool,
+    text: &'a str,
+}
+
+impl<'a> ConfigLine<'a> {
+    /// The rules of `data`, trimmed.
+    pub fn enumerate(path: &'a str, data: &'a str) -> impl Iterator<Item = ConfigLine<'a>> {
+        data.lines()
+            .enumerate()
+            .map(|(index, text)| (index + 1, text.trim()))
+            .filter(|(_, text)| !text.is_empty() && !text.starts_with('#'))
+            .enumerate()
+            .map(move |(rule, (number, text))| Self {
+                path,
+                number,
+                first: rule == 0,
+                text,
+            })
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// The value of a `key: value` leading rule, or `None` when this is
+    /// another rule. Fails when the leading rule is not the first one.
+    pub fn leading(&self, key: &str) -> Result<Option<&'a str>, Error> {
+        match self
+            .text
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            Some(value) if self.first => Ok(Some(value.trim())),
+            Some(_) => Err(self.error(format!("'{}' must come before any other rule", key))),
+            None => Ok(None),
+        }
+    }
+
+    pub fn error(&self, reason: impl Into<String>) -> Error {
+        Error::config(self.path, self.number, reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_column() {
+        let line = Line::new(2, 5, "A Q");
+        let token = line.text().split(' ').nth(1).unwrap();
+
+        match line.error(token, "invalid move") {
+            Error::Parse {
+                day,
+                line,
+                column,
+                text,
+                ..
+            } => {
+                assert_eq!(day, 2);
+                assert_eq!(line, 5);
+                assert_eq!(column, 3);
+                assert_eq!(text, "Q");
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_line_missing_token() {
+        let line = Line::new(9, 1, "R");
+
+        match line.missing("missing steps") {
+            Error::Parse { column, .. } => assert_eq!(column, 2),
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let line = Line::new(4, 3, "2-4,x-8");
+        let error = line.error(&line.text()[4..5], "invalid section");
+
+        assert_eq!(
+            error.to_string(),
+            "day 4, line 3, column 5: invalid section \"x\"\n    2-4,x-8\n        ^"
+        );
+
+        assert_eq!(
+            Error::overflow(1, "elf", 7).to_string(),
+            "day 1: arithmetic overflow at elf 7"
+        );
+    }
+
+    #[test]
+    fn test_config_lines() {
+        let data = "# Comment\n\n  cycle: a, b, c \nshape a: 1\ncycle: d\n";
+        let lines: Vec<ConfigLine> = ConfigLine::enumerate("rules.txt", data).collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].number(), 3);
+        assert_eq!(lines[0].text(), "cycle: a, b, c");
+        assert_eq!(lines[0].leading("cycle").unwrap(), Some("a, b, c"));
+        assert_eq!(lines[1].leading("cycle").unwrap(), None);
+
+        match lines[2].leading("cycle") {
+            Err(error) => assert_eq!(
+                error.to_string(),
+                "rules.txt:5: 'cycle' must come before any other rule"
+            ),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
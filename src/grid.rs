@@ -0,0 +1,326 @@
+use std::ops::{Add, Index, IndexMut, Sub};
+
+/// A position on a grid. `y` grows downwards, as rows do in puzzle input, so
+/// `Direction::Up` decreases it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// Clamps each coordinate to -1, 0 or 1, i.e. the single step that
+    /// moves towards this offset.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Number of steps between both points when diagonal steps are allowed.
+    pub fn chebyshev_distance(self, other: Self) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn offset(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::Down => Point::new(0, 1),
+            Self::Left => Point::new(-1, 0),
+            Self::Right => Point::new(1, 0),
+            Self::UpLeft => Point::new(-1, -1),
+            Self::UpRight => Point::new(1, -1),
+            Self::DownLeft => Point::new(-1, 1),
+            Self::DownRight => Point::new(1, 1),
+        }
+    }
+}
+
+/// A dense rectangular grid stored row by row, indexed by `Point` with `x`
+/// as the column and `y` as the row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Returns `None` when the rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let mut grid = Self::default();
+
+        for row in rows {
+            if grid.height > 0 && row.len() != grid.width {
+                return None;
+            }
+
+            grid.push_row(row);
+        }
+
+        Some(grid)
+    }
+
+    /// Appends a row at the bottom. The first row sets the width.
+    ///
+    /// Panics when the row's length differs from the width.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        if self.height == 0 {
+            self.width = row.len();
+        }
+
+        assert_eq!(
+            row.len(),
+            self.width,
+            "row length differs from the grid width"
+        );
+
+        self.cells.extend(row);
+        self.height += 1;
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The cells next to `point` in the given directions that are inside
+    /// the grid, e.g. `&Direction::ORTHOGONAL` or `&Direction::ALL`.
+    pub fn neighbours<'a>(
+        &'a self,
+        point: Point,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        directions.iter().filter_map(move |direction| {
+            let neighbour = point.step(*direction);
+
+            self.get(neighbour).map(|value| (neighbour, value))
+        })
+    }
+
+    /// The cells from `point` (excluded) up to the edge of the grid, walking
+    /// in `direction`.
+    pub fn ray(
+        &self,
+        point: Point,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let mut current = point;
+
+        std::iter::from_fn(move || {
+            current = current.step(direction);
+
+            self.get(current).map(|value| (current, value))
+        })
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_grid() -> Grid<u32> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = generate_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point::new(2, 0)], 3);
+        assert_eq!(grid[Point::new(0, 1)], 4);
+
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+        assert_eq!(Grid::<u32>::from_rows(Vec::new()), Some(Grid::default()));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = generate_grid();
+
+        assert!(grid.contains(Point::new(2, 1)));
+        assert!(!grid.contains(Point::new(3, 0)));
+        assert!(!grid.contains(Point::new(0, 2)));
+        assert!(!grid.contains(Point::new(-1, 0)));
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+
+        *grid.get_mut(Point::new(1, 1)).unwrap() = 9;
+        grid[Point::new(0, 0)] = 7;
+        assert_eq!(
+            grid.iter().map(|(_, value)| *value).collect::<Vec<_>>(),
+            [7, 2, 3, 4, 9, 6]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_outside() {
+        let grid = generate_grid();
+        let _ = grid[Point::new(3, 1)];
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = generate_grid();
+
+        let neighbours: Vec<u32> = grid
+            .neighbours(Point::new(0, 0), &Direction::ORTHOGONAL)
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(neighbours, [2, 4]);
+
+        let neighbours: Vec<u32> = grid
+            .neighbours(Point::new(1, 1), &Direction::ALL)
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(neighbours, [2, 3, 6, 4, 1]);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = generate_grid();
+
+        let ray: Vec<(Point, u32)> = grid
+            .ray(Point::new(0, 0), Direction::Right)
+            .map(|(point, value)| (point, *value))
+            .collect();
+        assert_eq!(ray, [(Point::new(1, 0), 2), (Point::new(2, 0), 3)]);
+
+        assert_eq!(grid.ray(Point::new(0, 0), Direction::DownRight).count(), 1);
+        assert_eq!(grid.ray(Point::new(0, 0), Direction::Up).count(), 0);
+    }
+
+    #[test]
+    fn test_point() {
+        let point = Point::new(2, -3);
+
+        assert_eq!(point.step(Direction::Up), Point::new(2, -4));
+        assert_eq!(point.step(Direction::DownLeft), Point::new(1, -2));
+        assert_eq!((point - Point::new(5, -3)).signum(), Point::new(-1, 0));
+        assert_eq!(point.chebyshev_distance(Point::new(0, 0)), 3);
+    }
+}
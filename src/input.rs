@@ -0,0 +1,117 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use crate::error::Error;
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    Path(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl Input {
+    /// The input checked in next to the day's code, independent of the
+    /// current working directory.
+    pub fn default_for(day: u8) -> Self {
+        let path = format!("{}/src/day{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+
+        Self::Path(PathBuf::from(path))
+    }
+
+    /// Parses a command line value, where `-` stands for stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Self::Stdin,
+            path => Self::Path(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            Self::Path(path) => {
+                fs::read_to_string(path).map_err(|error| Error::io(&self.to_string(), error))
+            }
+            Self::Stdin => {
+                let mut data = String::new();
+
+                io::stdin()
+                    .read_to_string(&mut data)
+                    .map_err(|error| Error::io(&self.to_string(), error))?;
+
+                Ok(data)
+            }
+            Self::Inline(data) => Ok(data.clone()),
+        }
+    }
+
+    /// Opens the input to be read line by line instead of all at once.
+    pub fn open(&self) -> Result<Box<dyn BufRead + '_>, Error> {
+        match self {
+            Self::Path(path) => {
+                let file = File::open(path).map_err(|error| Error::io(&self.to_string(), error))?;
+
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+            Self::Inline(data) => Ok(Box::new(data.as_bytes())),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+            Self::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Input::from_arg("-"), Input::Stdin);
+        assert_eq!(
+            Input::from_arg("day1.txt"),
+            Input::Path(PathBuf::from("day1.txt"))
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        let input = Input::Inline(String::from("A Y\nB X"));
+        assert_eq!(input.read().unwrap(), "A Y\nB X");
+
+        let input = Input::default_for(6);
+        assert!(input.read().unwrap().starts_with("bvsvcsssfw"));
+
+        let input = Input::from_arg("src/day0/input.txt");
+        assert!(matches!(input.read(), Err(Error::Io { .. })));
+    }
+
+    #[test]
+    fn test_open() {
+        let input = Input::Inline(String::from(
+            "A Y
+B X",
+        ));
+        let lines: Vec<String> = input.open().unwrap().lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["A Y", "B X"]);
+
+        let input = Input::default_for(6);
+        let mut line = String::new();
+        input.open().unwrap().read_line(&mut line).unwrap();
+        assert!(line.starts_with("bvsvcsssfw"));
+
+        let input = Input::from_arg("src/day0/input.txt");
+        assert!(matches!(input.open(), Err(Error::Io { .. })));
+    }
+}
@@ -0,0 +1,96 @@
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod output;
+pub mod runner;
+pub mod solution;
+pub mod verify;
+
+pub mod day1 {
+    mod lib;
+    mod stats;
+
+    pub use lib::{Day1, Elf, ElfList};
+    pub use stats::{Bucket, Statistics};
+}
+
+pub mod day2 {
+    mod analysis;
+    mod lib;
+    mod ruleset;
+    mod transcript;
+
+    pub use analysis::{Analysis, Interpretation, Reading};
+    pub use lib::{Day2, Game, MatchResult, Move, MoveCycle, Moves, Round, StrategyGuide};
+    pub use ruleset::Ruleset;
+    pub use transcript::{RoundRecord, Transcript};
+}
+
+pub mod day3 {
+    mod catalog;
+    mod lib;
+    mod validation;
+
+    pub use catalog::ItemCatalog;
+    pub use lib::{Compartment, Day3, Rucksacks};
+    pub use validation::{Finding, Issue, Validation};
+}
+
+pub mod day4 {
+    mod coverage;
+    mod index;
+    mod lib;
+
+    pub use coverage::Coverage;
+    pub use index::AssignmentIndex;
+    pub use lib::{Assignment, Day4, Group};
+}
+
+pub mod day5 {
+    mod lib;
+
+    pub use lib::{Action, Crate, CratesPort, Day5, Rearrangement};
+}
+
+pub mod day6 {
+    mod lib;
+
+    pub use lib::Day6;
+}
+
+pub mod day7 {
+    mod lib;
+
+    pub use lib::{Day7, FileSystem, FileSystemItemId};
+}
+
+pub mod day8 {
+    mod lib;
+
+    pub use lib::{Day8, Forest, Tree};
+}
+
+pub mod day9 {
+    mod lib;
+
+    pub use lib::{Day9, Knot};
+}
+
+use runner::Runner;
+
+/// A runner with every solved day registered.
+pub fn runner() -> Runner {
+    let mut runner = Runner::new();
+    runner.register::<day1::Day1>();
+    runner.register::<day2::Day2>();
+    runner.register::<day3::Day3>();
+    runner.register::<day4::Day4>();
+    runner.register::<day5::Day5>();
+    runner.register::<day6::Day6>();
+    runner.register::<day7::Day7>();
+    runner.register::<day8::Day8>();
+    runner.register::<day9::Day9>();
+
+    runner
+}
@@ -1,11 +1,481 @@
-mod day9 {
-    mod lib;
+use std::io::{self, Write};
+use std::{env, process};
 
-    pub use lib::Day9;
+use aoc_2022::bench::{self, Baseline};
+use aoc_2022::day1::Day1;
+use aoc_2022::day2::{Day2, Ruleset};
+use aoc_2022::day3::{Day3, ItemCatalog};
+use aoc_2022::day4::{Coverage, Day4};
+use aoc_2022::error::Error;
+use aoc_2022::input::Input;
+use aoc_2022::output::Format;
+use aoc_2022::runner::{Part, Runner};
+use aoc_2022::solution::Solution;
+use aoc_2022::verify::{self, Answers, Check};
+
+const USAGE: &str = "Usage:
+    aoc_2022 <day|all> [--part 1|2] [--input PATH|- | --data TEXT] [--format text|json|tsv]
+    aoc_2022 <day> --config FILE [--part 1|2] [--input PATH|- | --data TEXT] [--format text|json|tsv]
+    aoc_2022 bench <day|all> [--iterations N] [--baseline FILE] [--save FILE] [--threshold PERCENT]
+                             [--input PATH|- | --data TEXT]
+    aoc_2022 verify [day|all]
+    aoc_2022 guide [--config FILE] [--input PATH|- | --data TEXT]
+    aoc_2022 validate [--config FILE] [--input PATH|- | --data TEXT]
+    aoc_2022 stats [--buckets N] [--input PATH|- | --data TEXT]
+    aoc_2022 coverage [--over K] [--input PATH|- | --data TEXT]
+    aoc_2022 overlaps [--over K] [--input PATH|- | --data TEXT]
+
+Day 2 and guide take a rules FILE. Day 3 and validate take an item catalog
+FILE of 'item: priority' lines, for at most 128 item types.";
+
+#[derive(PartialEq)]
+enum Command {
+    Run,
+    Bench,
+    Verify,
+    Guide,
+    Validate,
+    Stats,
+    Coverage,
+    Overlaps,
+}
+
+enum Target {
+    Day(u8),
+    All,
 }
 
-use day9::Day9;
+struct Args {
+    command: Command,
+    target: Target,
+    part: Option<Part>,
+    input: Option<Input>,
+    format: Format,
+    iterations: usize,
+    baseline: Option<String>,
+    save: Option<String>,
+    threshold: f64,
+    config: Option<String>,
+    buckets: usize,
+    over: Option<usize>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut command = Command::Run;
+        let mut target = None;
+        let mut part = None;
+        let mut input = None;
+        let mut format = None;
+        let mut iterations = None;
+        let mut baseline = None;
+        let mut save = None;
+        let mut threshold = None;
+        let mut config = None;
+        let mut buckets = None;
+        let mut over = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or(format!("Missing value for {}", name));
+
+            match arg.as_str() {
+                "--part" => {
+                    let value = value("--part")?;
+                    part = Some(Part::parse(&value).ok_or(format!("Invalid part: {}", value))?);
+                }
+                "--input" => input = Some(Input::from_arg(&value("--input")?)),
+                "--data" => input = Some(Input::Inline(value("--data")?)),
+                "--format" => {
+                    let value = value("--format")?;
+                    format =
+                        Some(Format::parse(&value).ok_or(format!("Invalid format: {}", value))?);
+                }
+                "--iterations" => {
+                    let value = value("--iterations")?;
+                    iterations = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|&iterations| iterations > 0)
+                            .ok_or(format!("Invalid iterations: {}", value))?,
+                    );
+                }
+                "--baseline" => baseline = Some(value("--baseline")?),
+                "--save" => save = Some(value("--save")?),
+                "--threshold" => {
+                    let value = value("--threshold")?;
+                    threshold = Some(
+                        value
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|threshold| *threshold >= 0.0)
+                            .ok_or(format!("Invalid threshold: {}", value))?,
+                    );
+                }
+                "--config" => config = Some(value("--config")?),
+                "--buckets" => {
+                    let value = value("--buckets")?;
+                    buckets = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|&buckets| buckets > 0)
+                            .ok_or(format!("Invalid buckets: {}", value))?,
+                    );
+                }
+                "bench" if target.is_none() && command == Command::Run => command = Command::Bench,
+                "verify" if target.is_none() && command == Command::Run => {
+                    command = Command::Verify
+                }
+                "guide" if target.is_none() && command == Command::Run => command = Command::Guide,
+                "validate" if target.is_none() && command == Command::Run => {
+                    command = Command::Validate
+                }
+                "--over" => {
+                    let value = value("--over")?;
+                    over = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| format!("Invalid over: {}", value))?,
+                    );
+                }
+                "stats" if target.is_none() && command == Command::Run => command = Command::Stats,
+                "coverage" if target.is_none() && command == Command::Run => {
+                    command = Command::Coverage
+                }
+                "overlaps" if target.is_none() && command == Command::Run => {
+                    command = Command::Overlaps
+                }
+                "all" if target.is_none() => target = Some(Target::All),
+                day if target.is_none() => {
+                    let day = day.parse().map_err(|_| format!("Invalid day: {}", day))?;
+                    target = Some(Target::Day(day));
+                }
+                other => return Err(format!("Unexpected argument: {}", other)),
+            }
+        }
+
+        let target = match (target, &command) {
+            (Some(_), Command::Guide) => return Err(String::from("guide always analyses day 2")),
+            (Some(_), Command::Validate) => {
+                return Err(String::from("validate always checks day 3"))
+            }
+            (Some(_), Command::Stats) => return Err(String::from("stats always summarises day 1")),
+            (Some(_), Command::Coverage | Command::Overlaps) => {
+                return Err(String::from("coverage and overlaps always analyse day 4"))
+            }
+            (Some(target), _) => target,
+            (None, Command::Verify) => Target::All,
+            (None, Command::Guide) => Target::Day(2),
+            (None, Command::Validate) => Target::Day(3),
+            (None, Command::Stats) => Target::Day(1),
+            (None, Command::Coverage | Command::Overlaps) => Target::Day(4),
+            (None, _) => return Err(String::from("Missing day")),
+        };
+
+        if matches!(target, Target::All) && input.is_some() {
+            return Err(String::from("--input and --data cannot be used with all"));
+        }
+
+        let run_options = part.is_some() || format.is_some();
+        let bench_options =
+            iterations.is_some() || baseline.is_some() || save.is_some() || threshold.is_some();
+
+        match command {
+            _ if buckets.is_some() && command != Command::Stats => {
+                return Err(String::from("--buckets needs stats"));
+            }
+            _ if over.is_some() && !matches!(command, Command::Coverage | Command::Overlaps) => {
+                return Err(String::from("--over needs coverage or overlaps"));
+            }
+            Command::Run if bench_options => {
+                return Err(String::from(
+                    "--iterations, --baseline, --save and --threshold need bench",
+                ));
+            }
+            Command::Bench if run_options => {
+                return Err(String::from(
+                    "--part and --format cannot be used with bench",
+                ));
+            }
+            Command::Verify if run_options || bench_options || input.is_some() => {
+                return Err(String::from(
+                    "verify checks the checked-in inputs and takes no options",
+                ));
+            }
+            Command::Guide if run_options || bench_options => {
+                return Err(String::from(
+                    "guide only takes --config, --input and --data",
+                ));
+            }
+            Command::Validate if run_options || bench_options => {
+                return Err(String::from(
+                    "validate only takes --config, --input and --data",
+                ));
+            }
+            Command::Stats if run_options || bench_options || config.is_some() => {
+                return Err(String::from(
+                    "stats only takes --buckets, --input and --data",
+                ));
+            }
+            Command::Coverage | Command::Overlaps
+                if run_options || bench_options || config.is_some() =>
+            {
+                return Err(String::from(
+                    "coverage and overlaps only take --over, --input and --data",
+                ));
+            }
+            Command::Run if config.is_some() && matches!(target, Target::All) => {
+                return Err(String::from("--config cannot be used with all"));
+            }
+            Command::Bench | Command::Verify if config.is_some() => {
+                return Err(String::from("--config needs a day run, guide or validate"));
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            command,
+            target,
+            part,
+            input,
+            format: format.unwrap_or(Format::Text),
+            iterations: iterations.unwrap_or(10),
+            baseline,
+            save,
+            threshold: threshold.unwrap_or(10.0) / 100.0,
+            config,
+            buckets: buckets.unwrap_or(10),
+            over,
+        })
+    }
+}
 
 fn main() {
-    Day9::run();
-}
\ No newline at end of file
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let runner = aoc_2022::runner();
+
+    let days = match args.target {
+        Target::All => runner
+            .days()
+            .map(|day| (day, Input::default_for(day)))
+            .collect(),
+        Target::Day(day) => {
+            if !runner.days().any(|number| number == day) {
+                let days: Vec<String> = runner.days().map(|day| day.to_string()).collect();
+                eprintln!("Unknown day: {} (available: {})", day, days.join(", "));
+                process::exit(2);
+            }
+
+            vec![(
+                day,
+                args.input
+                    .clone()
+                    .unwrap_or_else(|| Input::default_for(day)),
+            )]
+        }
+    };
+
+    let result = match args.command {
+        Command::Run => run(&runner, &days, &args),
+        Command::Bench => bench(&runner, &days, &args),
+        Command::Verify => verify(&runner, &days),
+        Command::Guide => guide(&days[0].1, &args),
+        Command::Validate => validate(&days[0].1, &args),
+        Command::Stats => stats(&days[0].1, &args),
+        Command::Coverage => coverage(&days[0].1, &args),
+        Command::Overlaps => overlaps(&days[0].1, &args),
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn stdout_error(error: io::Error) -> Error {
+    Error::io("<stdout>", error)
+}
+
+/// Returns `false` when any day failed. The other days are still run, each
+/// error being reported as it happens.
+fn run(runner: &Runner, days: &[(u8, Input)], args: &Args) -> Result<bool, Error> {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    // Settings only come with a single day, and are read before any output
+    let configured = match (&args.config, days) {
+        (Some(path), [(day, _)]) => runner.configure(*day, path).transpose()?,
+        _ => None,
+    };
+
+    let mut out = io::stdout().lock();
+    args.format.write_header(&mut out).map_err(stdout_error)?;
+
+    let mut failed = false;
+
+    for (day, input) in days {
+        let answers = match &configured {
+            Some(configured) => Some(configured(input, &parts)),
+            None => runner.run(*day, input, &parts),
+        };
+
+        match answers {
+            Some(Ok(answers)) => args
+                .format
+                .write_answers(&mut out, *day, input, &answers)
+                .map_err(stdout_error)?,
+            Some(Err(error)) => {
+                eprintln!("Error: {}", error);
+                failed = true;
+            }
+            None => {}
+        }
+    }
+
+    out.flush().map_err(stdout_error)?;
+
+    Ok(!failed)
+}
+
+/// Returns `false` when any stage regressed against the baseline.
+fn bench(runner: &Runner, days: &[(u8, Input)], args: &Args) -> Result<bool, Error> {
+    let baseline = args.baseline.as_deref().map(Baseline::load).transpose()?;
+
+    let mut measurements = Vec::new();
+    for (day, input) in days {
+        if let Some(day_measurements) = runner.bench(*day, input, args.iterations) {
+            measurements.extend(day_measurements?);
+        }
+    }
+
+    let mut out = io::stdout().lock();
+    let regressions =
+        bench::write_report(&mut out, &measurements, baseline.as_ref(), args.threshold)
+            .map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    if let Some(path) = &args.save {
+        Baseline::save(path, &measurements)?;
+    }
+
+    Ok(regressions == 0)
+}
+
+/// Returns `false` when any answer differs from the recorded one.
+fn verify(runner: &Runner, days: &[(u8, Input)]) -> Result<bool, Error> {
+    let parts = [Part::One, Part::Two];
+    let mut checks = Vec::new();
+
+    for (day, input) in days {
+        let answers = Answers::load(&Answers::default_path(*day))?;
+
+        match runner.run(*day, input, &parts) {
+            Some(Ok(day_answers)) => checks.extend(
+                day_answers
+                    .iter()
+                    .map(|answer| Check::new(*day, answer, &answers)),
+            ),
+            Some(Err(error)) => checks.extend(
+                parts
+                    .iter()
+                    .map(|part| Check::error(*day, *part, &error, &answers)),
+            ),
+            None => {}
+        }
+    }
+
+    let mut out = io::stdout().lock();
+    let failures = verify::write_report(&mut out, &checks).map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    Ok(failures == 0)
+}
+
+/// Prints the score of each reading of the strategy guide, best first.
+fn guide(input: &Input, args: &Args) -> Result<bool, Error> {
+    let ruleset = match &args.config {
+        Some(path) => Ruleset::load(path)?,
+        None => Ruleset::default(),
+    };
+
+    let analysis = Day2::analyse(&input.read()?, &ruleset)?;
+
+    let mut out = io::stdout().lock();
+    analysis.write_report(&mut out).map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    Ok(true)
+}
+
+/// Prints every issue with the rucksacks. Returns `false` when there is any.
+fn validate(input: &Input, args: &Args) -> Result<bool, Error> {
+    let catalog = match &args.config {
+        Some(path) => ItemCatalog::load(path)?,
+        None => ItemCatalog::default(),
+    };
+
+    let validation = Day3::validate(&input.read()?, Day3::GROUP_SIZE, &catalog);
+
+    let mut out = io::stdout().lock();
+    validation.write_report(&mut out).map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    Ok(validation.is_valid())
+}
+
+/// Prints a summary of the calories carried by the elves.
+fn stats(input: &Input, args: &Args) -> Result<bool, Error> {
+    let elf_list = Day1::parse_reader(&input.to_string(), input.open()?)?;
+    let statistics = elf_list
+        .statistics(args.buckets)
+        .ok_or_else(|| Error::solve(Day1::DAY, "there are no elves"))?;
+
+    let mut out = io::stdout().lock();
+    statistics.write_report(&mut out).map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    Ok(true)
+}
+
+/// Prints how the sections of the camp are covered by every assignment,
+/// flagging those covered by more than `--over` of them, 1 by default.
+fn coverage(input: &Input, args: &Args) -> Result<bool, Error> {
+    let groups = Day4::parse(&input.read()?)?;
+    let coverage = Coverage::analyse(
+        groups.iter().flat_map(|group| group.assignments()),
+        args.over.unwrap_or(1),
+    )
+    .ok_or_else(|| Error::solve(Day4::DAY, "there are no assignments"))?;
+
+    let mut out = io::stdout().lock();
+    coverage.write_report(&mut out).map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    Ok(true)
+}
+
+/// Prints which members of each group overlap, leaving out the groups with
+/// at most `--over` overlapping pairs.
+fn overlaps(input: &Input, args: &Args) -> Result<bool, Error> {
+    let groups = Day4::parse(&input.read()?)?;
+
+    let mut out = io::stdout().lock();
+    Day4::write_overlaps(&mut out, &groups, args.over).map_err(stdout_error)?;
+    out.flush().map_err(stdout_error)?;
+
+    Ok(true)
+}
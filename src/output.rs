@@ -0,0 +1,177 @@
+use std::io::{self, Write};
+
+use crate::input::Input;
+use crate::solution::Answer;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "tsv" => Some(Self::Tsv),
+            _ => None,
+        }
+    }
+
+    /// Written once before any answer. Only TSV has one.
+    pub fn write_header(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Self::Tsv => writeln!(out, "day\tpart\tanswer\telapsed_ns\tinput"),
+            Self::Text | Self::Json => Ok(()),
+        }
+    }
+
+    /// Writes one record per answer: a labelled block for text, and one
+    /// line per (day, part) for JSON lines and TSV.
+    pub fn write_answers(
+        &self,
+        out: &mut impl Write,
+        day: u8,
+        input: &Input,
+        answers: &[Answer],
+    ) -> io::Result<()> {
+        if *self == Self::Text {
+            writeln!(out, "Day {}", day)?;
+        }
+
+        for answer in answers {
+            let elapsed = answer.elapsed.as_nanos();
+
+            match self {
+                Self::Text => writeln!(
+                    out,
+                    "Part {} - {}: {}",
+                    answer.part, answer.label, answer.value
+                )?,
+                Self::Json => writeln!(
+                    out,
+                    "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ns\":{},\"input\":{}}}",
+                    day,
+                    answer.part,
+                    json_string(&answer.value),
+                    elapsed,
+                    json_string(&input.to_string())
+                )?,
+                Self::Tsv => writeln!(
+                    out,
+                    "{}\t{}\t{}\t{}\t{}",
+                    day,
+                    answer.part,
+                    tsv_field(&answer.value),
+                    elapsed,
+                    tsv_field(&input.to_string())
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn tsv_field(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use crate::runner::Part;
+
+    fn generate_answers() -> Vec<Answer> {
+        vec![
+            Answer {
+                part: Part::One,
+                label: "Msg from crates on top",
+                value: String::from("CMZ"),
+                elapsed: Duration::from_nanos(1500),
+            },
+            Answer {
+                part: Part::Two,
+                label: "Msg from crates on top",
+                value: String::from("M\tC\"D"),
+                elapsed: Duration::from_nanos(20),
+            },
+        ]
+    }
+
+    fn render(format: Format) -> String {
+        let mut out = Vec::new();
+        let input = Input::Inline(String::new());
+
+        format.write_header(&mut out).unwrap();
+        format
+            .write_answers(&mut out, 5, &input, &generate_answers())
+            .unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_format_parse() {
+        assert_eq!(Format::parse("text"), Some(Format::Text));
+        assert_eq!(Format::parse("json"), Some(Format::Json));
+        assert_eq!(Format::parse("tsv"), Some(Format::Tsv));
+        assert_eq!(Format::parse("csv"), None);
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            render(Format::Text),
+            "Day 5\nPart 1 - Msg from crates on top: CMZ\nPart 2 - Msg from crates on top: M\tC\"D\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            render(Format::Json),
+            "{\"day\":5,\"part\":1,\"answer\":\"CMZ\",\"elapsed_ns\":1500,\"input\":\"<inline>\"}\n\
+             {\"day\":5,\"part\":2,\"answer\":\"M\\tC\\\"D\",\"elapsed_ns\":20,\"input\":\"<inline>\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            render(Format::Tsv),
+            "day\tpart\tanswer\telapsed_ns\tinput\n\
+             5\t1\tCMZ\t1500\t<inline>\n\
+             5\t2\tM\\tC\"D\t20\t<inline>\n"
+        );
+    }
+}
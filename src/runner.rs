@@ -0,0 +1,174 @@
+use std::fmt;
+
+use crate::bench::{self, Measurement};
+use crate::error::Error;
+use crate::input::Input;
+use crate::solution::{Answer, Configured, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn parse(part: &str) -> Option<Self> {
+        match part {
+            "1" => Some(Self::One),
+            "2" => Some(Self::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::One => f.pad("1"),
+            Self::Two => f.pad("2"),
+        }
+    }
+}
+
+struct Day {
+    number: u8,
+    run: fn(&Input, &[Part]) -> Result<Vec<Answer>, Error>,
+    bench: fn(&str, usize) -> Result<Vec<Measurement>, Error>,
+    configure: fn(&str) -> Result<Configured, Error>,
+}
+
+#[derive(Default)]
+pub struct Runner {
+    days: Vec<Day>,
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self { days: Vec::new() }
+    }
+
+    pub fn register<S: Solution>(&mut self) {
+        self.days.push(Day {
+            number: S::DAY,
+            run: S::run,
+            bench: bench::bench::<S>,
+            configure: S::configure,
+        });
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.days.iter().map(|day| day.number)
+    }
+
+    /// Returns `None` when `day` is not registered.
+    pub fn run(
+        &self,
+        day: u8,
+        input: &Input,
+        parts: &[Part],
+    ) -> Option<Result<Vec<Answer>, Error>> {
+        let day = self.get(day)?;
+
+        Some((day.run)(input, parts))
+    }
+
+    /// Returns `None` when `day` is not registered.
+    pub fn bench(
+        &self,
+        day: u8,
+        input: &Input,
+        iterations: usize,
+    ) -> Option<Result<Vec<Measurement>, Error>> {
+        let day = self.get(day)?;
+
+        Some(input.read().and_then(|data| (day.bench)(&data, iterations)))
+    }
+
+    /// Reads the settings of `day` from the file at `path`. Returns `None`
+    /// when `day` is not registered.
+    pub fn configure(&self, day: u8, path: &str) -> Option<Result<Configured, Error>> {
+        let day = self.get(day)?;
+
+        Some((day.configure)(path))
+    }
+
+    fn get(&self, day: u8) -> Option<&Day> {
+        self.days.iter().find(|registered| registered.number == day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_parse() {
+        assert_eq!(Part::parse("1"), Some(Part::One));
+        assert_eq!(Part::parse("2"), Some(Part::Two));
+        assert_eq!(Part::parse("3"), None);
+        assert_eq!(Part::parse("one"), None);
+    }
+
+    struct Echo {}
+
+    impl Solution for Echo {
+        const DAY: u8 = 1;
+
+        type Input = String;
+        type Part1 = String;
+        type Part2 = usize;
+
+        const PART1_LABEL: &'static str = "Input";
+        const PART2_LABEL: &'static str = "Length";
+
+        fn parse(data: &str) -> Result<String, Error> {
+            Ok(String::from(data))
+        }
+
+        fn part1(data: &String) -> Result<String, Error> {
+            Ok(data.clone())
+        }
+
+        fn part2(data: &String) -> Result<usize, Error> {
+            Ok(data.len())
+        }
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        let mut runner = Runner::new();
+        runner.register::<Echo>();
+
+        let input = Input::Inline(String::from("echo"));
+        let answers = runner.run(1, &input, &[Part::Two]).unwrap().unwrap();
+        assert_eq!(answers[0].value, "4");
+        assert!(runner.run(2, &input, &[Part::One]).is_none());
+        assert_eq!(runner.days().collect::<Vec<u8>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_bench() {
+        let mut runner = Runner::new();
+        runner.register::<Echo>();
+
+        let input = Input::Inline(String::from("echo"));
+        let measurements = runner.bench(1, &input, 3).unwrap().unwrap();
+        assert_eq!(measurements.len(), 3);
+        assert!(measurements
+            .iter()
+            .all(|measurement| measurement.bytes == 4));
+        assert!(runner.bench(2, &input, 3).is_none());
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut runner = Runner::new();
+        runner.register::<Echo>();
+
+        assert!(matches!(
+            runner.configure(1, "settings.txt"),
+            Some(Err(Error::Solve { day: 1, .. }))
+        ));
+        assert!(runner.configure(2, "settings.txt").is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::input::Input;
+use crate::runner::Part;
+
+/// The answer to one part of a day, with the time its solver took.
+#[derive(Debug)]
+pub struct Answer {
+    pub part: Part,
+    pub label: &'static str,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// A day run with settings read from a file, see `Solution::configure`.
+pub type Configured = Box<dyn Fn(&Input, &[Part]) -> Result<Vec<Answer>, Error>>;
+
+/// A puzzle solver: parses the raw input once into a typed model and
+/// answers both parts from it.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Text shown next to each answer when printing to the terminal.
+    const PART1_LABEL: &'static str;
+    const PART2_LABEL: &'static str;
+
+    fn parse(data: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error>;
+
+    /// Parses `data` and returns the requested answers, in the given order.
+    fn solve(data: &str, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+        let input = Self::parse(data)?;
+
+        Self::answers(&input, parts)
+    }
+
+    /// Returns the requested answers for an already parsed input, in the
+    /// given order.
+    fn answers(input: &Self::Input, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+        parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+
+                let (label, value) = match part {
+                    Part::One => (Self::PART1_LABEL, Self::part1(input)?.to_string()),
+                    Part::Two => (Self::PART2_LABEL, Self::part2(input)?.to_string()),
+                };
+
+                Ok(Answer {
+                    part: *part,
+                    label,
+                    value,
+                    elapsed: start.elapsed(),
+                })
+            })
+            .collect()
+    }
+
+    /// Reads `input` whole and solves it. Days that can parse as they read
+    /// override this to avoid holding the raw text in memory.
+    fn run(input: &Input, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+        let data = input.read()?;
+
+        Self::solve(&data, parts)
+    }
+
+    /// Reads the day's settings from the file at `path` and returns a `run`
+    /// that uses them. Days without settings reject any file.
+    fn configure(path: &str) -> Result<Configured, Error> {
+        Err(Error::solve(
+            Self::DAY,
+            format!("this day takes no settings file ({})", path),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount {}
+
+    impl Solution for LineCount {
+        const DAY: u8 = 0;
+
+        type Input = Vec<String>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        const PART1_LABEL: &'static str = "Lines";
+        const PART2_LABEL: &'static str = "Last line";
+
+        fn parse(data: &str) -> Result<Vec<String>, Error> {
+            Ok(data.lines().map(String::from).collect())
+        }
+
+        fn part1(lines: &Vec<String>) -> Result<usize, Error> {
+            Ok(lines.len())
+        }
+
+        fn part2(lines: &Vec<String>) -> Result<String, Error> {
+            lines
+                .last()
+                .cloned()
+                .ok_or_else(|| Error::solve(Self::DAY, "no lines"))
+        }
+    }
+
+    #[test]
+    fn test_solve_parts() {
+        let answers = LineCount::solve("a\nb\nc", &[Part::Two, Part::One]).unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].part, Part::Two);
+        assert_eq!(answers[0].label, "Last line");
+        assert_eq!(answers[0].value, "c");
+        assert_eq!(answers[1].part, Part::One);
+        assert_eq!(answers[1].value, "3");
+
+        let answers = LineCount::solve("a\nb\nc", &[Part::One]).unwrap();
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].value, "3");
+
+        assert!(LineCount::solve("", &[Part::Two]).is_err());
+    }
+
+    #[test]
+    fn test_no_settings() {
+        match LineCount::configure("settings.txt") {
+            Err(Error::Solve { day, reason }) => {
+                assert_eq!(day, 0);
+                assert_eq!(reason, "this day takes no settings file (settings.txt)");
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+            Ok(_) => panic!("Unexpected settings"),
+        }
+    }
+}
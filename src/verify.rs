@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::runner::Part;
+use crate::solution::Answer;
+
+/// The known-good answers for a day's checked-in input, one `partN: answer`
+/// line per part.
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<Part, String>,
+}
+
+impl Answers {
+    pub fn default_path(day: u8) -> PathBuf {
+        let path = format!("{}/src/day{}/answers.txt", env!("CARGO_MANIFEST_DIR"), day);
+
+        PathBuf::from(path)
+    }
+
+    /// A missing file means no answer is known yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let name = path.display().to_string();
+
+        match fs::read_to_string(path) {
+            Ok(data) => Self::parse(&name, &data),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(Error::io(&name, error)),
+        }
+    }
+
+    fn parse(path: &str, data: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or_else(|| Error::config(path, index + 1, "expected 'partN: answer'"))?;
+
+            let part = part
+                .trim()
+                .strip_prefix("part")
+                .and_then(Part::parse)
+                .ok_or_else(|| Error::config(path, index + 1, "expected part1 or part2"))?;
+
+            answers.expected.insert(part, String::from(answer.trim()));
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        self.expected.get(&part).map(String::as_str)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pass => f.pad("pass"),
+            Self::Fail => f.pad("FAIL"),
+            Self::Missing => f.pad("missing"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub status: Status,
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl Check {
+    pub fn new(day: u8, answer: &Answer, answers: &Answers) -> Self {
+        let expected = answers.get(answer.part);
+
+        let status = match expected {
+            Some(expected) if expected == answer.value => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Missing,
+        };
+
+        Self {
+            day,
+            part: answer.part,
+            status,
+            expected: expected.map(String::from),
+            actual: answer.value.clone(),
+        }
+    }
+
+    /// A day that could not be solved fails every part it has an answer for.
+    pub fn error(day: u8, part: Part, error: &Error, answers: &Answers) -> Self {
+        let expected = answers.get(part);
+
+        Self {
+            day,
+            part,
+            status: if expected.is_some() {
+                Status::Fail
+            } else {
+                Status::Missing
+            },
+            expected: expected.map(String::from),
+            actual: format!(
+                "error: {}",
+                error.to_string().lines().next().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Prints one row per check and returns the number of failures.
+pub fn write_report(out: &mut impl Write, checks: &[Check]) -> io::Result<usize> {
+    writeln!(
+        out,
+        "{:<4} {:<4} {:<8} {:<16} actual",
+        "day", "part", "status", "expected"
+    )?;
+
+    for check in checks {
+        writeln!(
+            out,
+            "{:<4} {:<4} {:<8} {:<16} {}",
+            check.day,
+            check.part,
+            check.status,
+            check.expected.as_deref().unwrap_or("-"),
+            check.actual
+        )?;
+    }
+
+    let count = |status| checks.iter().filter(|check| check.status == status).count();
+    let failures = count(Status::Fail);
+
+    writeln!(
+        out,
+        "{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        failures,
+        count(Status::Missing)
+    )?;
+
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    fn answer(part: Part, value: &str) -> Answer {
+        Answer {
+            part,
+            label: "",
+            value: String::from(value),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("answers.txt", "part1: CMZ\n\npart2:  MCD \n").unwrap();
+        assert_eq!(answers.get(Part::One), Some("CMZ"));
+        assert_eq!(answers.get(Part::Two), Some("MCD"));
+
+        let answers = Answers::parse("answers.txt", "part2: 45000\n").unwrap();
+        assert_eq!(answers.get(Part::One), None);
+
+        match Answers::parse("answers.txt", "part1: 1\npart3: 2\n") {
+            Err(Error::Config { line, .. }) => assert_eq!(line, 2),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        assert!(Answers::parse("answers.txt", "24000").is_err());
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let answers = Answers::load(Path::new("src/day0/answers.txt")).unwrap();
+        assert_eq!(answers.get(Part::One), None);
+    }
+
+    #[test]
+    fn test_checks() {
+        let answers = Answers::parse("answers.txt", "part1: 24000").unwrap();
+
+        let check = Check::new(1, &answer(Part::One, "24000"), &answers);
+        assert_eq!(check.status, Status::Pass);
+
+        let check = Check::new(1, &answer(Part::One, "24001"), &answers);
+        assert_eq!(check.status, Status::Fail);
+        assert_eq!(check.expected.as_deref(), Some("24000"));
+
+        let check = Check::new(1, &answer(Part::Two, "45000"), &answers);
+        assert_eq!(check.status, Status::Missing);
+
+        let error = Error::solve(1, "there are no elves");
+        assert_eq!(
+            Check::error(1, Part::One, &error, &answers).status,
+            Status::Fail
+        );
+        assert_eq!(
+            Check::error(1, Part::Two, &error, &answers).status,
+            Status::Missing
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let answers = Answers::parse("answers.txt", "part1: 24000\npart2: 45000").unwrap();
+        let checks = vec![
+            Check::new(1, &answer(Part::One, "24000"), &answers),
+            Check::new(1, &answer(Part::Two, "41000"), &answers),
+            Check::new(2, &answer(Part::One, "15"), &Answers::default()),
+        ];
+
+        let mut out = Vec::new();
+        let failures = write_report(&mut out, &checks).unwrap();
+        let report = String::from_utf8(out).unwrap();
+
+        assert_eq!(failures, 1);
+        assert_eq!(report.lines().last(), Some("1 passed, 1 failed, 1 missing"));
+    }
+
+    #[test]
+    fn test_checked_in_answers() {
+        for day in 1..=9 {
+            let answers = Answers::load(&Answers::default_path(day)).unwrap();
+            assert!(answers.get(Part::One).is_some());
+            assert!(answers.get(Part::Two).is_some());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use aoc_2022::day7::FileSystem;
+use aoc_2022::day9::Knot;
+use aoc_2022::grid::{Direction, Point};
+use aoc_2022::input::Input;
+use aoc_2022::runner::Part;
+
+fn example(day: u8) -> Input {
+    let path = format!("{}/src/day{}/example.txt", env!("CARGO_MANIFEST_DIR"), day);
+
+    Input::Path(PathBuf::from(path))
+}
+
+#[test]
+fn test_examples() {
+    let expected = [
+        (1, "24000", "45000"),
+        (2, "15", "12"),
+        (3, "157", "70"),
+        (4, "2", "4"),
+        (5, "CMZ", "MCD"),
+        (6, "7", "19"),
+        (7, "95437", "24933642"),
+        (8, "21", "8"),
+        (9, "13", "1"),
+    ];
+
+    let runner = aoc_2022::runner();
+    assert_eq!(runner.days().count(), expected.len());
+
+    for (day, part1, part2) in expected {
+        let answers = runner
+            .run(day, &example(day), &[Part::One, Part::Two])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(answers[0].value, part1, "day {} part 1", day);
+        assert_eq!(answers[1].value, part2, "day {} part 2", day);
+    }
+}
+
+#[test]
+fn test_larger_example() {
+    let path = format!(
+        "{}/src/day9/example_larger.txt",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let answers = aoc_2022::runner()
+        .run(9, &Input::Path(PathBuf::from(path)), &[Part::Two])
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(answers[0].value, "36");
+}
+
+#[test]
+fn test_filesystem() {
+    let mut filesystem = FileSystem::new();
+    filesystem.add_folder("a");
+    filesystem.add_file("b.txt", 100);
+    filesystem.change_dir("a").unwrap();
+    filesystem.add_file("c.txt", 50);
+
+    assert_eq!(filesystem.simple_total_size(100), 50);
+    assert_eq!(filesystem.folder_removal_selection(200, 100), Some(50));
+    assert!(filesystem.change_dir("missing").is_err());
+}
+
+#[test]
+fn test_knot() {
+    let mut head = Knot::new(0, 0);
+    let mut tail = Knot::new(0, 0);
+
+    head.step(Direction::Right);
+    assert!(!tail.follow(&head));
+
+    head.step(Direction::Right);
+    assert!(tail.follow(&head));
+    assert_eq!(tail.position(), Point::new(1, 0));
+}